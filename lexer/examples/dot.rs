@@ -0,0 +1,13 @@
+/* lexer/examples/dot.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Prints the lexer's state graph. Run this to update `doc/lexer.dot`:
+//!
+//!     cargo run --example dot > ../doc/lexer.dot
+
+extern crate sibillexer;
+
+fn main() {
+    print!("{}", sibillexer::graph::dot());
+}
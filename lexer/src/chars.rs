@@ -2,26 +2,53 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
+use sibiltypes::char::ident;
+
 pub trait Lexable {
+    fn is_backquote(&self) -> bool;
+    fn is_comma(&self) -> bool;
     fn is_dot(&self) -> bool;
+    fn is_double_quote(&self) -> bool;
     fn is_exactness(&self) -> bool;
+    fn is_exponent_marker(&self) -> bool;
     fn is_hash(&self) -> bool;
     fn is_identifier_delimiter(&self) -> bool;
     fn is_identifier_initial(&self) -> bool;
     fn is_identifier_subsequent(&self) -> bool;
+    fn is_sign_subsequent(&self) -> bool;
+    fn is_dot_subsequent(&self) -> bool;
     fn is_left_paren(&self) -> bool;
+    fn is_pipe(&self) -> bool;
     fn is_quote(&self) -> bool;
     fn is_radix(&self) -> bool;
     fn is_right_paren(&self) -> bool;
+    fn is_semicolon(&self) -> bool;
 }
 
 impl Lexable for char {
+    fn is_backquote(&self) -> bool {
+        *self == '`'
+    }
+
+    fn is_comma(&self) -> bool {
+        *self == ','
+    }
+
     fn is_dot(&self) -> bool {
         *self == '.'
     }
 
+    fn is_double_quote(&self) -> bool {
+        *self == '"'
+    }
+
     fn is_exactness(&self) -> bool {
-        *self == 'i' || *self == 'e'
+        let c = self.to_ascii_lowercase();
+        c == 'i' || c == 'e'
+    }
+
+    fn is_exponent_marker(&self) -> bool {
+        *self == 'e' || *self == 'E'
     }
 
     fn is_left_paren(&self) -> bool {
@@ -32,16 +59,33 @@ impl Lexable for char {
         *self == ')'
     }
 
+    fn is_semicolon(&self) -> bool {
+        *self == ';'
+    }
+
+    fn is_pipe(&self) -> bool {
+        *self == '|'
+    }
+
     fn is_identifier_initial(&self) -> bool {
-        self.is_alphabetic() || self.is_special_initial() || self.is_explicit_sign()
+        ident::is_initial(*self)
     }
 
     fn is_identifier_subsequent(&self) -> bool {
-        self.is_identifier_initial() || self.is_numeric() || self.is_special_subsequent()
+        ident::is_subsequent(*self)
+    }
+
+    fn is_sign_subsequent(&self) -> bool {
+        ident::is_sign_subsequent(*self)
+    }
+
+    fn is_dot_subsequent(&self) -> bool {
+        ident::is_dot_subsequent(*self)
     }
 
     fn is_identifier_delimiter(&self) -> bool {
-        self.is_whitespace() || self.is_left_paren() || self.is_right_paren()
+        self.is_whitespace() || self.is_left_paren() || self.is_right_paren() || self.is_double_quote()
+            || self.is_semicolon() || self.is_pipe()
     }
 
     fn is_quote(&self) -> bool {
@@ -50,30 +94,10 @@ impl Lexable for char {
 
     fn is_radix(&self) -> bool {
         let radishes = &['b', 'd', 'o', 'x'];
-        radishes.contains(self)
+        radishes.contains(&self.to_ascii_lowercase())
     }
 
     fn is_hash(&self) -> bool {
         *self == '#'
     }
 }
-
-trait LexableSpecial {
-    fn is_special_initial(&self) -> bool;
-    fn is_special_subsequent(&self) -> bool;
-    fn is_explicit_sign(&self) -> bool;
-}
-
-impl LexableSpecial for char {
-    fn is_special_initial(&self) -> bool {
-        "!$%&*/:<=>?~_^".contains(*self)
-    }
-
-    fn is_special_subsequent(&self) -> bool {
-        self.is_explicit_sign() || ".@".contains(*self)
-    }
-
-    fn is_explicit_sign(&self) -> bool {
-        *self == '+' || *self == '-'
-    }
-}
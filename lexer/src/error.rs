@@ -2,25 +2,129 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
-#[derive(Debug, Eq, PartialEq)]
+use std::error;
+use std::fmt;
+use std::io;
+use std::rc::Rc;
+use span::Pos;
+
+/// The kinds of problems the lexer can find in its input.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// A string or `|` quoted identifier is missing its closing quote.
+    UnterminatedString,
+    /// A backslash escape in a string or `|` quoted identifier isn't valid.
+    BadEscape,
+    /// A number literal is malformed or can't be represented.
+    BadNumber,
+    /// A character can't appear where it was found.
+    InvalidChar,
+    /// A `#n=` datum label or `#n#` reference has a number that's too big.
+    BadLabel,
+    /// A `#!` directive isn't one the lexer knows.
+    UnknownDirective,
+    /// The input ended in the middle of a token.
+    UnexpectedEof,
+    /// The input isn't valid UTF-8.
+    InvalidUtf8,
+    /// The input couldn't be read.
+    Io,
+    /// A resumable `Lexer` ran out of input in the middle of a token. Lexing picks up where it
+    /// left off once there's more input.
+    NeedMoreInput,
+}
+
+/// An error found while lexing. The location is filled in by the `Lexer` when it yields the
+/// error.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Error {
-    message: String
+    kind: ErrorKind,
+    message: String,
+    file: Option<Rc<str>>,
+    pos: Pos,
+    /// How many bytes of input the error covers, for errors in decoding the input itself.
+    width: usize,
 }
 
 impl Error {
-    pub fn new(msg: String) -> Error {
+    pub fn new(kind: ErrorKind, msg: String) -> Error {
         Error {
-            message: msg
+            kind,
+            message: msg,
+            file: None,
+            pos: Pos::start(),
+            width: 0,
         }
     }
 
     pub fn invalid_char(c: char) -> Error {
-        Error::new(format!("invalid character: {}", c))
+        Error::new(ErrorKind::InvalidChar, format!("invalid character: {}", c))
+    }
+
+    pub fn bad_escape(c: char) -> Error {
+        Error::new(ErrorKind::BadEscape, format!("invalid escape sequence: \\{}", c))
+    }
+
+    pub fn bad_number(msg: &str) -> Error {
+        Error::new(ErrorKind::BadNumber, format!("bad number: {}", msg))
+    }
+
+    pub fn unterminated_string() -> Error {
+        Error::new(ErrorKind::UnterminatedString, "unterminated string".to_string())
+    }
+
+    pub fn unterminated_identifier() -> Error {
+        Error::new(ErrorKind::UnterminatedString, "unterminated identifier".to_string())
+    }
+
+    pub fn unknown_directive(name: &str) -> Error {
+        Error::new(ErrorKind::UnknownDirective, format!("unknown directive: #!{}", name))
     }
 
     pub fn unexpected_eof() -> Error {
-        Error::new("unexpected EOF".to_string())
+        Error::new(ErrorKind::UnexpectedEof, "unexpected EOF".to_string())
     }
 
+    pub fn invalid_utf8(bytes: &[u8]) -> Error {
+        let hex: Vec<String> = bytes.iter().map(|b| format!("\\x{:02x}", b)).collect();
+        let mut err = Error::new(ErrorKind::InvalidUtf8, format!("invalid UTF-8: {}", hex.concat()));
+        err.width = bytes.len();
+        err
+    }
+
+    pub fn io(err: &io::Error) -> Error {
+        Error::new(ErrorKind::Io, format!("couldn't read input: {}", err))
+    }
+
+    pub fn need_more_input() -> Error {
+        Error::new(ErrorKind::NeedMoreInput, "need more input".to_string())
+    }
+
+    /// Place this error at the given location in the input.
+    pub(crate) fn at(mut self, file: Option<Rc<str>>, pos: Pos) -> Error {
+        self.file = file;
+        self.pos = pos;
+        self
+    }
+
+    pub(crate) fn width(&self) -> usize { self.width }
+
+    pub fn kind(&self) -> ErrorKind { self.kind }
     pub fn msg(&self) -> &str { &self.message }
+    pub fn file(&self) -> Option<&str> { self.file.as_deref() }
+    pub fn pos(&self) -> Pos { self.pos }
+    pub fn line(&self) -> usize { self.pos.line() }
+    pub fn column(&self) -> usize { self.pos.column() }
+    pub fn offset(&self) -> usize { self.pos.offset() }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref file) = self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}: {}", self.pos, self.message)
+    }
 }
+
+impl error::Error for Error { }
@@ -0,0 +1,145 @@
+/* lexer/src/graph.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # State Graph
+//!
+//! Exports the lexer's state machine as a Graphviz graph, which is checked in as `doc/lexer.dot`.
+//! The states don't declare their transitions, so the graph is found by running them: starting
+//! from `Begin`, every character in a sample alphabet is fed to the state reached by each input
+//! seen so far, and any input that takes a transition nobody's taken yet is explored further.
+//! States are named by their types, and tokens are drawn as accepting nodes named by their
+//! variants, like `"Token::Bool"`.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use states::{Begin, State, StateResult};
+
+/// The characters fed to each state: printable ASCII, some whitespace, and a couple of non-ASCII
+/// letters to stand in for the rest of Unicode.
+fn alphabet() -> Vec<char> {
+    let mut chars: Vec<char> = (0x20u8..0x7F).map(|b| b as char).collect();
+    chars.extend(&['\t', '\n', '\r', 'λ', 'ß']);
+    chars
+}
+
+/// Where a state goes on a character.
+enum Step {
+    /// To another state, or the same one.
+    State(&'static str),
+    /// Out of the state machine, with a token.
+    Token(String),
+    /// Out of the state machine, with no token.
+    Stop,
+}
+
+/// Run the state machine over `input`, and return the state it ends up in, or None if it left the
+/// state machine before the end of `input`.
+fn replay(input: &[char]) -> Option<Box<State>> {
+    let mut state: Box<State> = Box::new(Begin::new());
+    for &c in input {
+        match state.lex(c) {
+            StateResult::Continue => {},
+            StateResult::Advance { to } => state = to,
+            _ => return None,
+        }
+    }
+    Some(state)
+}
+
+fn step(state: &mut Box<State>, c: char) -> Step {
+    match state.lex(c) {
+        StateResult::Continue => Step::State(state.name()),
+        StateResult::Advance { to } => Step::State(to.name()),
+        StateResult::Emit(token, _) => {
+            // Drop the token's payload, if it has one.
+            let name = format!("{:?}", token);
+            Step::Token(format!("\"Token::{}\"", name.split('(').next().unwrap_or("")))
+        },
+        StateResult::Discard(_) | StateResult::FoldCase(_) | StateResult::Fail(_) => Step::Stop,
+    }
+}
+
+/// The edges of the state graph, from a state to a state or token, with the characters that take
+/// them.
+type Edges = BTreeMap<(&'static str, String), BTreeSet<char>>;
+
+fn explore() -> (Edges, BTreeSet<String>) {
+    let alphabet = alphabet();
+    let mut edges = Edges::new();
+    let mut tokens = BTreeSet::new();
+    let mut queue: VecDeque<Vec<char>> = VecDeque::new();
+    queue.push_back(Vec::new());
+    while let Some(input) = queue.pop_front() {
+        for &c in &alphabet {
+            let mut state = match replay(&input) {
+                Some(state) => state,
+                None => break,
+            };
+            let from = state.name();
+            let (to, is_state) = match step(&mut state, c) {
+                Step::State(to) => (to.to_string(), true),
+                Step::Token(token) => {
+                    tokens.insert(token.clone());
+                    (token, false)
+                },
+                Step::Stop => continue,
+            };
+            let new = edges.entry((from, to)).or_default().insert(c);
+            if new && is_state {
+                let mut next = input.clone();
+                next.push(c);
+                queue.push_back(next);
+            }
+        }
+    }
+    (edges, tokens)
+}
+
+/// Write a set of characters as a label, collapsing runs of three or more consecutive characters
+/// into ranges like `0-9`.
+fn label(chars: &BTreeSet<char>) -> String {
+    let chars: Vec<char> = chars.iter().cloned().collect();
+    let mut parts: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let mut j = i;
+        while j + 1 < chars.len() && chars[j + 1] as u32 == chars[j] as u32 + 1 {
+            j += 1;
+        }
+        if j - i >= 2 {
+            parts.push(format!("{}-{}", escape(chars[i]), escape(chars[j])));
+        } else {
+            parts.extend(chars[i..j + 1].iter().map(|c| escape(*c)));
+        }
+        i = j + 1;
+    }
+    parts.join(",")
+}
+
+fn escape(c: char) -> String {
+    match c {
+        ' ' => "SP".to_string(),
+        '\t' => "\\\\t".to_string(),
+        '\n' => "\\\\n".to_string(),
+        '\r' => "\\\\r".to_string(),
+        '"' => "\\\"".to_string(),
+        '\\' => "\\\\".to_string(),
+        _ => c.to_string(),
+    }
+}
+
+/// The lexer's state graph in Graphviz's DOT language.
+pub fn dot() -> String {
+    let (edges, tokens) = explore();
+    let mut out = String::new();
+    out.push_str("digraph lexer {\n");
+    out.push_str("    rankdir=LR;\n");
+    let tokens: Vec<String> = tokens.into_iter().collect();
+    out.push_str(&format!("    node [shape = doublecircle] {};\n", tokens.join(" ")));
+    out.push_str("    node [shape = circle];\n");
+    for ((from, to), chars) in &edges {
+        out.push_str(&format!("    {} -> {} [ label = \"{}\" ];\n", from, to, label(chars)));
+    }
+    out.push_str("}\n");
+    out
+}
@@ -0,0 +1,142 @@
+/* lexer/src/input.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # Input
+//!
+//! Where a `Lexer` gets its characters. Any iterator of `char`s is an `Input`. `Utf8Reader`
+//! decodes characters from a `BufRead` as the lexer asks for them, and `Feed` holds input that
+//! arrives a piece at a time, like lines typed into a REPL.
+
+use std::collections::VecDeque;
+use std::io::{BufRead, ErrorKind as IoErrorKind};
+use std::str;
+use error::{Error, ErrorKind};
+
+/// A source of characters for a `Lexer`.
+pub trait Input {
+    /// The next character, or an error if the input couldn't be read or decoded. Returns `None`
+    /// if there isn't any more input, at least for now; see `is_open`.
+    fn read_char(&mut self) -> Option<Result<char, Error>>;
+
+    /// Whether more input might show up after `read_char` returns `None`.
+    fn is_open(&self) -> bool {
+        false
+    }
+}
+
+impl<I> Input for I where I: Iterator<Item=char> {
+    fn read_char(&mut self) -> Option<Result<char, Error>> {
+        self.next().map(Ok)
+    }
+}
+
+/// Decodes UTF-8 from a `BufRead` one character at a time. Invalid byte sequences are reported
+/// as `InvalidUtf8` errors, and reading carries on after them.
+#[derive(Debug)]
+pub struct Utf8Reader<R> {
+    reader: R,
+    done: bool,
+}
+
+impl<R> Utf8Reader<R> where R: BufRead {
+    pub fn new(reader: R) -> Utf8Reader<R> {
+        Utf8Reader { reader, done: false }
+    }
+
+    /// Look at the next byte without consuming it, retrying if the read is interrupted.
+    fn peek_byte(&mut self) -> Result<Option<u8>, Error> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => return Ok(buf.first().cloned()),
+                Err(ref err) if err.kind() == IoErrorKind::Interrupted => continue,
+                Err(err) => return Err(Error::io(&err)),
+            }
+        }
+    }
+
+    fn decode(&mut self) -> Result<Option<char>, Error> {
+        let first = match self.peek_byte()? {
+            Some(b) => b,
+            None => return Ok(None),
+        };
+        self.reader.consume(1);
+        let width = match first {
+            0x00..=0x7F => return Ok(Some(first as char)),
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => return Err(Error::invalid_utf8(&[first])),
+        };
+        let mut bytes = vec![first];
+        while bytes.len() < width {
+            // A byte that can't continue the sequence starts the next one, so leave it be.
+            match self.peek_byte()? {
+                Some(b) if b & 0xC0 == 0x80 => {
+                    self.reader.consume(1);
+                    bytes.push(b);
+                },
+                _ => return Err(Error::invalid_utf8(&bytes)),
+            }
+        }
+        match str::from_utf8(&bytes) {
+            Ok(s) => Ok(s.chars().next()),
+            Err(_) => Err(Error::invalid_utf8(&bytes)),
+        }
+    }
+}
+
+impl<R> Input for Utf8Reader<R> where R: BufRead {
+    fn read_char(&mut self) -> Option<Result<char, Error>> {
+        if self.done {
+            return None;
+        }
+        match self.decode() {
+            Ok(Some(c)) => Some(Ok(c)),
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(err) => {
+                // There's no telling what a failed reader will do next, so stop reading it.
+                if err.kind() == ErrorKind::Io {
+                    self.done = true;
+                }
+                Some(Err(err))
+            },
+        }
+    }
+}
+
+/// Input that's handed to the lexer a piece at a time. The input stays open until `finish` is
+/// called, so running out of characters means waiting for more rather than the end of input.
+#[derive(Debug, Default)]
+pub struct Feed {
+    chars: VecDeque<char>,
+    finished: bool,
+}
+
+impl Feed {
+    pub fn new() -> Feed {
+        Feed { chars: VecDeque::new(), finished: false }
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        self.chars.extend(text.chars());
+    }
+
+    /// Mark the end of the input.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+}
+
+impl Input for Feed {
+    fn read_char(&mut self) -> Option<Result<char, Error>> {
+        self.chars.pop_front().map(Ok)
+    }
+
+    fn is_open(&self) -> bool {
+        !self.finished
+    }
+}
@@ -4,90 +4,246 @@
 
 extern crate sibiltypes;
 
-use std::iter::Peekable;
-use states::{Begin, Resume, StateResult};
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::mem;
+use std::rc::Rc;
+use sibiltypes::char::fold;
+use input::{Feed, Input, Utf8Reader};
+use states::{Begin, Resume, State, StateResult};
+use trace::{Event, Trace};
 
 mod chars;
 mod error;
+pub mod graph;
+pub mod input;
+mod span;
 mod states;
 mod token;
+pub mod trace;
 
-pub use error::Error;
-pub use token::{Lex, Token};
+pub use error::{Error, ErrorKind};
+pub use span::{Pos, Span};
+pub use token::{Lex, Num, Token, Trivia, TriviaKind};
 
 pub type Result = std::result::Result<Lex, Error>;
 
-pub struct Lexer<T> where T: Iterator<Item=char> {
+pub struct Lexer<T> where T: Input {
     /// The input stream.
-    input: Peekable<T>,
+    input: T,
+    /// The next character of input, if it's been read already.
+    peeked: Option<std::result::Result<char, Error>>,
 
-    /// Current line number.
-    line: usize,
-    /// Character offset from the start of the input.
-    offset: usize,
+    /// The name of the file the input comes from, if it comes from a file.
+    file: Option<Rc<str>>,
+    /// Position of the next character of input.
+    pos: Pos,
+
+    /// Whether identifiers and character names are case folded, as if by `#!fold-case`.
+    fold_case: bool,
+
+    /// After an error, the state that skips the rest of the bad token.
+    recovery: Option<Partial>,
+
+    /// The token a resumable lexer was in the middle of when it ran out of input.
+    partial: Option<Partial>,
+
+    /// Whether to keep whitespace and comments as Trivia on the Lexes around them.
+    keep_trivia: bool,
+    /// Trivia read since the last token.
+    trivia: Vec<Trivia>,
+    /// The last token, held until its trailing trivia has been read.
+    held: Option<Lex>,
+    /// Output that's ready to go.
+    ready: VecDeque<Result>,
+    /// Whether the Eof token has been emitted.
+    at_eof: bool,
+
+    /// Where to report what the lexer is doing, if anywhere.
+    trace: Option<Box<Trace>>,
 }
 
-impl<T> Lexer<T> where T: Iterator<Item=char> {
+/// A token that's waiting for more input, or the remains of a bad one.
+struct Partial {
+    state: Box<State>,
+    buffer: String,
+    start: Pos,
+}
+
+impl<T> Lexer<T> where T: Input {
     pub fn new(input: T) -> Lexer<T> {
         Lexer {
-            input: input.peekable(),
-            line: 0,
-            offset: 0,
+            input,
+            peeked: None,
+            file: None,
+            pos: Pos::start(),
+            fold_case: false,
+            recovery: None,
+            partial: None,
+            keep_trivia: false,
+            trivia: Vec::new(),
+            held: None,
+            ready: VecDeque::new(),
+            at_eof: false,
+            trace: None,
+        }
+    }
+
+    /// Create a Lexer for input read from the file named `file`. The name is included in the
+    /// spans of Lexes and in errors.
+    pub fn with_file(input: T, file: &str) -> Lexer<T> {
+        let mut lexer = Lexer::new(input);
+        lexer.file = Some(Rc::from(file));
+        lexer
+    }
+
+    /// Keep whitespace, line endings, comments and the text of bad tokens as Trivia attached to
+    /// the Lexes around them, and end the output with an `Eof` token that holds whatever trivia is
+    /// at the end of the input. Concatenating the `full_text` of every Lex then reproduces the
+    /// input exactly, as long as it's valid UTF-8.
+    pub fn set_keep_trivia(&mut self, keep: bool) {
+        self.keep_trivia = keep;
+    }
+
+    /// Fold the case of identifiers and character names, as if the input started with
+    /// `#!fold-case`. Identifiers written between `|`s are left alone. Directives in the input
+    /// override this from where they appear.
+    pub fn set_fold_case(&mut self, fold: bool) {
+        self.fold_case = fold;
+    }
+
+    /// Report what the lexer is doing to `sink`. See the `trace` module.
+    pub fn set_trace<S>(&mut self, sink: S) where S: Trace + 'static {
+        self.trace = Some(Box::new(sink));
+    }
+
+    fn peek(&mut self) -> Option<&std::result::Result<char, Error>> {
+        if self.peeked.is_none() {
+            self.peeked = self.input.read_char();
         }
+        self.peeked.as_ref()
     }
 
-    fn next(&mut self) -> Option<T::Item> {
-        let out = self.input.next();
-        if let Some(c) = out {
+    fn next(&mut self) -> Option<std::result::Result<char, Error>> {
+        self.peek();
+        let out = self.peeked.take();
+        if let Some(Ok(c)) = out {
             self.update_offsets(c);
         }
         out
     }
 
-    fn handle_error(&self, err: Error) {
-        panic!("{}:{}: {}", self.line, self.offset, err.msg())
+    fn update_offsets(&mut self, c: char) {
+        self.pos = self.pos.after(c);
     }
 
-    fn prepare_offsets(&mut self) { }
+    fn lex(&self, token: Token, value: &str, start: Pos) -> Lex {
+        let token = match token {
+            Token::Id(ref name) if self.fold_case && !value.starts_with('|') => {
+                Token::Id(fold::fold_case(name))
+            },
+            token => token,
+        };
+        Lex::new(token, value, Span::new(start, self.pos).in_file(self.file.clone()))
+    }
 
-    fn update_offsets(&mut self, c: char) {
-        match c {
-            '\n' => {
-                self.line += 1;
-                self.offset = 0;
+    fn trace(&mut self, event: Event) {
+        if let Some(ref mut sink) = self.trace {
+            sink.trace(&event);
+        }
+    }
+
+    fn emit(&mut self, out: Result) -> Option<Result> {
+        match out {
+            Ok(ref lex) => self.trace(Event::Emit(lex)),
+            Err(ref error) => {
+                let recovery = self.recovery.as_ref().map_or("Begin", |r| r.state.name());
+                self.trace(Event::Error { error, recovery });
             },
-            _ => self.offset += 1
         }
-        println!("incremented offsets {}:{}", self.line, self.offset);
+        Some(out)
     }
 }
 
-impl<T> Iterator for Lexer<T> where T: Iterator<Item=char> {
-    type Item = Result;
+impl<R> Lexer<Utf8Reader<R>> where R: BufRead {
+    /// Create a Lexer that reads UTF-8 input from `reader` as it goes.
+    pub fn from_reader(reader: R) -> Lexer<Utf8Reader<R>> {
+        Lexer::new(Utf8Reader::new(reader))
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.prepare_offsets();
+impl Lexer<Feed> {
+    /// Create a Lexer for input that arrives a piece at a time, e.g. lines typed into a REPL.
+    /// Running out of input in the middle of a token produces a `NeedMoreInput` error rather than
+    /// a failure, and lexing picks the token back up after the next `feed`. Call `finish` at the
+    /// end of the input.
+    pub fn resumable() -> Lexer<Feed> {
+        Lexer::new(Feed::new())
+    }
 
-        let mut token_line = self.line;
-        let mut token_offset = self.offset;
-        println!("beginning token at {}:{}", token_line, token_offset);
+    pub fn feed(&mut self, text: &str) {
+        self.input.push_str(text);
+    }
+
+    pub fn finish(&mut self) {
+        self.input.finish();
+    }
+}
 
-        let mut buffer = String::new();
-        let mut state: Box<states::State> = Box::new(Begin::new());
-        let mut out: Option<Self::Item> = None;
+impl<T> Lexer<T> where T: Input {
+    /// Lex the next token.
+    fn next_token(&mut self) -> Option<Result> {
+        let (mut state, mut buffer, mut token_start) = match self.partial.take() {
+            Some(partial) => (partial.state, partial.buffer, partial.start),
+            None => {
+                let start = self.pos;
+                self.trace(Event::Begin(start));
+                match self.recovery.take() {
+                    Some(recovery) => (recovery.state, recovery.buffer, recovery.start),
+                    None => (Box::new(Begin::with_fold_case(self.fold_case)) as Box<State>, String::new(), start),
+                }
+            },
+        };
         loop {
-            let peek = self.input.peek().map(char::clone);
-            println!("lexing {:?} in state {:?}, buffer = {:?}", peek, state, buffer);
+            let peek = self.peek().cloned();
             match peek {
+                None if self.input.is_open() => {
+                    // Hang on to the token until there's more input.
+                    let unfinished = !state.between_tokens();
+                    self.partial = Some(Partial { state, buffer, start: token_start });
+                    if unfinished {
+                        let file = self.file.clone();
+                        return Some(Err(Error::need_more_input().at(file, token_start)));
+                    }
+                    return None;
+                },
                 None => match state.none() {
-                    Ok(None) => break,
+                    Ok(None) => {
+                        self.discard(state.trivia(), &buffer, token_start);
+                        return None;
+                    },
                     Ok(Some(token)) => {
-                        out = Some(Ok(Lex::new(token, &buffer, token_line, token_offset)));
-                        break;
+                        let lex = self.lex(token, &buffer, token_start);
+                        return self.emit(Ok(lex));
+                    },
+                    // Running out of input leaves the current token unfinished, so report the
+                    // error where that token started.
+                    Err(err) => {
+                        self.discard(TriviaKind::Skipped, &buffer, token_start);
+                        let file = self.file.clone();
+                        return self.emit(Err(err.at(file, token_start)));
                     },
-                    Err(err) => self.handle_error(err)
                 },
-                Some(c) => {
+                Some(Err(err)) => {
+                    // Bad input is treated like a bad character in the current token.
+                    self.peeked = None;
+                    let pos = self.pos;
+                    self.pos = self.pos.after_bytes(err.width());
+                    self.recovery = Some(Partial { state: state.recover(), buffer, start: token_start });
+                    let file = self.file.clone();
+                    return self.emit(Err(err.at(file, pos)));
+                },
+                Some(Ok(c)) => {
                     let result = state.lex(c);
                     match result {
                         StateResult::Continue => {
@@ -97,31 +253,102 @@ impl<T> Iterator for Lexer<T> where T: Iterator<Item=char> {
                         StateResult::Advance { to } => {
                             buffer.push(c);
                             self.next();
+                            self.trace(Event::Transition { from: state.name(), to: to.name(), c });
                             state = to;
                         },
-                        StateResult::Discard(resume) => {
-                            buffer.clear();
-                            state = Box::new(Begin::new());
-                            if resume == Resume::AtNext {
-                                self.next();
+                        StateResult::Discard(_) | StateResult::FoldCase(_) => {
+                            self.trace(Event::Discard { state: state.name() });
+                            match result {
+                                StateResult::Discard(Resume::AtNext) => {
+                                    buffer.push(c);
+                                    self.next();
+                                },
+                                StateResult::FoldCase(fold) => self.fold_case = fold,
+                                _ => {},
                             }
-                            token_line = self.line;
-                            token_offset = self.offset;
+                            self.discard(state.trivia(), &buffer, token_start);
+                            buffer.clear();
+                            state = Box::new(Begin::with_fold_case(self.fold_case));
+                            token_start = self.pos;
+                            self.trace(Event::Begin(token_start));
                         },
                         StateResult::Emit(token, resume) => {
                             if resume == Resume::AtNext {
                                 buffer.push(c);
                                 self.next();
                             }
-                            out = Some(Ok(Lex::new(token, &buffer, token_line, token_offset)));
-                            break;
+                            let lex = self.lex(token, &buffer, token_start);
+                            return self.emit(Ok(lex));
+                        },
+                        StateResult::Fail(err) => {
+                            // Skip the rest of the bad token the next time around, starting with
+                            // this character.
+                            let recovery = state.recover();
+                            self.recovery = Some(Partial { state: recovery, buffer, start: token_start });
+                            let (file, pos) = (self.file.clone(), self.pos);
+                            return self.emit(Err(err.at(file, pos)));
                         },
-                        StateResult::Fail(err) => self.handle_error(err),
                     }
                 },
             }
         }
-        println!("emitting {:?}", out);
-        out
+    }
+
+    /// Keep `text`, which the lexer just threw away, as trivia if we're keeping trivia.
+    fn discard(&mut self, kind: TriviaKind, text: &str, start: Pos) {
+        if self.keep_trivia && !text.is_empty() {
+            let trivia = Trivia::split(kind, text, start, self.file.clone());
+            self.trivia.extend(trivia);
+        }
+    }
+
+    /// Give the trivia read so far to the held token and to `lex`, and hold on to `lex` until its
+    /// trailing trivia has been read.
+    fn attach_trivia(&mut self, mut lex: Lex) {
+        self.release_held();
+        lex.set_leading(mem::take(&mut self.trivia));
+        self.held = Some(lex);
+    }
+
+    /// Give the held token the trivia read so far, through the end of its line, and send it out.
+    fn release_held(&mut self) {
+        if let Some(mut lex) = self.held.take() {
+            let end = self.trivia.iter()
+                .position(|t| t.kind() == TriviaKind::Newline)
+                .map_or(self.trivia.len(), |i| i + 1);
+            let rest = self.trivia.split_off(end);
+            lex.set_trailing(mem::replace(&mut self.trivia, rest));
+            self.ready.push_back(Ok(lex));
+        }
+    }
+}
+
+impl<T> Iterator for Lexer<T> where T: Input {
+    type Item = Result;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.keep_trivia {
+            return self.next_token();
+        }
+        loop {
+            if let Some(out) = self.ready.pop_front() {
+                return Some(out);
+            }
+            match self.next_token() {
+                Some(Ok(lex)) => self.attach_trivia(lex),
+                Some(Err(err)) => {
+                    // The trivia before the bad token is complete, so the held token can go.
+                    self.release_held();
+                    self.ready.push_back(Err(err));
+                },
+                None if self.at_eof || self.input.is_open() => return None,
+                None => {
+                    let span = Span::new(self.pos, self.pos).in_file(self.file.clone());
+                    self.attach_trivia(Lex::new(Token::Eof, "", span));
+                    self.release_held();
+                    self.at_eof = true;
+                },
+            }
+        }
     }
 }
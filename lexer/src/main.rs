@@ -6,25 +6,39 @@ extern crate sibillexer;
 
 use std::io;
 use std::io::Write;
-use sibillexer::Lexer;
+use sibillexer::{ErrorKind, Lexer};
+
+fn prompt(p: &str) {
+    print!("{}", p);
+    io::stdout().flush().expect("couldn't flush");
+}
 
 fn main() {
+    let mut lexer = Lexer::resumable();
+    prompt("> ");
     loop {
-        // Print a prompt.
-        print!("> ");
-        io::stdout().flush().ok().expect("couldn't flush");
-
-        // Read a line from stdin.
+        // Read a line from stdin, and stop at the end of input.
         let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
-
-        // Create a lexer and parser and process the input.
-        let lexer = Lexer::new(input.chars());
+        if io::stdin().read_line(&mut input).unwrap() == 0 {
+            lexer.finish();
+        } else {
+            lexer.feed(&input);
+        }
 
-        // Print the parser's output.
-        for thing in lexer {
-            println!("{:?}", thing);
+        // Print the lexer's output. Keep reading lines until the last token is finished.
+        let mut continued = false;
+        for thing in lexer.by_ref() {
+            match thing {
+                Err(ref err) if err.kind() == ErrorKind::NeedMoreInput => {
+                    continued = true;
+                    break;
+                },
+                _ => println!("{:?}", thing),
+            }
+        }
+        if input.is_empty() {
+            break;
         }
-        println!();
+        prompt(if continued { ". " } else { "> " });
     }
 }
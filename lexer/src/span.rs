@@ -0,0 +1,103 @@
+/* lexer/src/span.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # Source Locations
+//!
+//! A `Pos` is a point in the input and a `Span` is the range of input between two of them. Lines
+//! and columns count from 1, like they do in most editors; byte offsets count from 0.
+
+use std::fmt;
+use std::rc::Rc;
+
+/// A point in the input. The column counts characters, not bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Pos {
+    line: usize,
+    column: usize,
+    offset: usize,
+}
+
+/// A range of the input, from `start` up to but not including `end`, optionally naming the file
+/// the input came from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Span {
+    file: Option<Rc<str>>,
+    start: Pos,
+    end: Pos,
+}
+
+impl Pos {
+    pub fn new(line: usize, column: usize, offset: usize) -> Pos {
+        Pos { line, column, offset }
+    }
+
+    /// The position of the first character of the input.
+    pub fn start() -> Pos {
+        Pos::new(1, 1, 0)
+    }
+
+    /// The position just after `c`, if `c` is at this position.
+    pub fn after(&self, c: char) -> Pos {
+        match c {
+            '\n' => Pos::new(self.line + 1, 1, self.offset + 1),
+            _ => Pos::new(self.line, self.column + 1, self.offset + c.len_utf8()),
+        }
+    }
+
+    /// The position just after `len` bytes of input that aren't a character, e.g. because they
+    /// aren't valid UTF-8. They take up one column.
+    pub fn after_bytes(&self, len: usize) -> Pos {
+        match len {
+            0 => *self,
+            _ => Pos::new(self.line, self.column + 1, self.offset + len),
+        }
+    }
+
+    /// The position just after `s`, if `s` starts at this position.
+    pub fn after_str(&self, s: &str) -> Pos {
+        s.chars().fold(*self, |pos, c| pos.after(c))
+    }
+
+    pub fn line(&self) -> usize { self.line }
+    pub fn column(&self) -> usize { self.column }
+    pub fn offset(&self) -> usize { self.offset }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl Span {
+    pub fn new(start: Pos, end: Pos) -> Span {
+        Span { file: None, start, end }
+    }
+
+    /// This span, in the file named `file`.
+    pub fn in_file(mut self, file: Option<Rc<str>>) -> Span {
+        self.file = file;
+        self
+    }
+
+    /// The span from the start of this span to the end of `other`.
+    pub fn to(&self, other: &Span) -> Span {
+        Span { file: self.file.clone(), start: self.start, end: other.end }
+    }
+
+    pub fn file(&self) -> Option<&str> { self.file.as_deref() }
+    pub fn start(&self) -> Pos { self.start }
+    pub fn end(&self) -> Pos { self.end }
+}
+
+/// Spans are written as the file name, if there is one, and the start of the span, e.g.
+/// `main.scm:3:14`.
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref file) = self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}", self.start)
+    }
+}
@@ -6,17 +6,26 @@ use chars::Lexable;
 use error::Error;
 use token::Token;
 use states::{Resume, State, StateResult};
+use states::comma::Comma;
+use states::comment::LineComment;
 use states::dot::Dot;
 use states::id::IdSub;
 use states::hash::Hash;
 use states::number::{Builder, Digit};
+use states::number::sign::Sign;
+use states::string::Str;
 use states::whitespace::Whitespace;
 
-#[derive(Debug)] pub struct Begin;
+/// The start of a token. Holds whether case folding is on, for the character names that follow.
+#[derive(Debug)] pub struct Begin(bool);
 
 impl Begin {
     pub fn new() -> Begin {
-        Begin{}
+        Begin(false)
+    }
+
+    pub fn with_fold_case(fold: bool) -> Begin {
+        Begin(fold)
     }
 }
 
@@ -30,14 +39,26 @@ impl State for Begin {
             StateResult::Emit(Token::RightParen, Resume::AtNext)
         } else if c.is_dot() {
             StateResult::advance(Box::new(Dot::new()))
+        } else if let Some(st) = Sign::with_char(&Builder::new(), c) {
+            StateResult::advance(Box::new(st))
         } else if c.is_identifier_initial() {
-            StateResult::advance(Box::new(IdSub{}))
+            StateResult::advance(Box::new(IdSub::with_char(c)))
         } else if c.is_hash() {
-            StateResult::advance(Box::new(Hash::new()))
+            StateResult::advance(Box::new(Hash::new(self.0)))
         } else if let Some(st) = Digit::with_char(&Builder::new(), c) {
             StateResult::advance(Box::new(st))
         } else if c.is_quote() {
             StateResult::Emit(Token::Quote, Resume::AtNext)
+        } else if c.is_backquote() {
+            StateResult::Emit(Token::Quasiquote, Resume::AtNext)
+        } else if c.is_comma() {
+            StateResult::advance(Box::new(Comma::new()))
+        } else if c.is_semicolon() {
+            StateResult::advance(Box::new(LineComment::new()))
+        } else if c.is_double_quote() {
+            StateResult::advance(Box::new(Str::new()))
+        } else if c.is_pipe() {
+            StateResult::advance(Box::new(Str::pipe()))
         } else {
             StateResult::fail(Error::invalid_char(c))
         }
@@ -46,4 +67,8 @@ impl State for Begin {
     fn none(&mut self) -> Result<Option<Token>, Error> {
         Ok(None)
     }
+
+    fn between_tokens(&self) -> bool {
+        true
+    }
 }
@@ -2,7 +2,7 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
-use error::Error;
+use error::{Error, ErrorKind};
 use chars::Lexable;
 use states::{Resume, State, StateResult};
 use token::Token;
@@ -55,7 +55,7 @@ impl State for Bool {
     fn none(&mut self) -> Result<Option<Token>, Error> {
         match self.handle_delimiter() {
             Some(token) => Ok(Some(token)),
-            None => Err(Error::new("Found EOF while trying to parse a bool".to_string()))
+            None => Err(Error::new(ErrorKind::UnexpectedEof, "Found EOF while trying to parse a bool".to_string()))
         }
     }
 }
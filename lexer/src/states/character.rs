@@ -0,0 +1,77 @@
+/* lexer/src/states/character.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use sibiltypes::char::{fold, names};
+use chars::Lexable;
+use error::{Error, ErrorKind};
+use states::{Resume, State, StateResult};
+use token::Token;
+
+/// A `#\` that has not yet seen the character it introduces. Holds whether case folding is on.
+#[derive(Debug)] pub struct Char(bool);
+
+/// A `#\` followed by one or more characters. This is either a single character, a named
+/// character like `#\newline`, or a hex scalar value like `#\x41`. Names are case folded when
+/// folding is on, but single characters never are.
+#[derive(Debug)]
+pub struct CharName {
+    name: String,
+    fold: bool,
+}
+
+impl Char {
+    pub fn new(fold: bool) -> Char {
+        Char(fold)
+    }
+}
+
+impl State for Char {
+    fn lex(&mut self, c: char) -> StateResult {
+        // Any character at all can follow the slash, even delimiters like `(` or ` `.
+        StateResult::advance(Box::new(CharName { name: c.to_string(), fold: self.0 }))
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Err(Error::unexpected_eof())
+    }
+}
+
+impl CharName {
+    fn resolve(&self) -> Result<Token, Error> {
+        let mut chars = self.name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Token::Char(c));
+        }
+
+        let name = if self.fold { fold::fold_case(&self.name) } else { self.name.clone() };
+        let scalar = match name.chars().next() {
+            Some('x') if name[1..].chars().all(|c| c.is_ascii_hexdigit()) => {
+                u32::from_str_radix(&name[1..], 16).ok().and_then(::std::char::from_u32)
+            },
+            _ => None,
+        };
+        match scalar.or_else(|| names::char_for(&name)) {
+            Some(c) => Ok(Token::Char(c)),
+            None => Err(Error::new(ErrorKind::InvalidChar, format!("unknown character name: {}", self.name))),
+        }
+    }
+}
+
+impl State for CharName {
+    fn lex(&mut self, c: char) -> StateResult {
+        if c.is_identifier_delimiter() {
+            match self.resolve() {
+                Ok(token) => StateResult::emit(token, Resume::Here),
+                Err(err) => StateResult::fail(err),
+            }
+        } else {
+            self.name.push(c);
+            StateResult::Continue
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        self.resolve().map(Some)
+    }
+}
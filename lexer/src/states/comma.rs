@@ -0,0 +1,30 @@
+/* lexer/src/states/comma.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use error::Error;
+use states::{Resume, State, StateResult};
+use token::Token;
+
+/// A `,`, which is either an unquote by itself or the start of an unquote-splicing `,@`.
+#[derive(Debug)] pub struct Comma;
+
+impl Comma {
+    pub fn new() -> Comma {
+        Comma{}
+    }
+}
+
+impl State for Comma {
+    fn lex(&mut self, c: char) -> StateResult {
+        if c == '@' {
+            StateResult::emit(Token::UnquoteSplicing, Resume::AtNext)
+        } else {
+            StateResult::emit(Token::Unquote, Resume::Here)
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Ok(Some(Token::Unquote))
+    }
+}
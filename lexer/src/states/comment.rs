@@ -0,0 +1,79 @@
+/* lexer/src/states/comment.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use error::{Error, ErrorKind};
+use states::{Resume, State, StateResult};
+use token::{Token, TriviaKind};
+
+/// A `;` comment, which runs to the end of the line.
+#[derive(Debug)] pub struct LineComment;
+
+/// A `#| ... |#` comment. These can be nested, so keep track of how deep we are.
+#[derive(Debug)]
+pub struct BlockComment {
+    depth: usize,
+    last: Option<char>,
+}
+
+impl LineComment {
+    pub fn new() -> LineComment {
+        LineComment{}
+    }
+}
+
+impl State for LineComment {
+    fn lex(&mut self, c: char) -> StateResult {
+        if c == '\n' {
+            StateResult::Discard(Resume::AtNext)
+        } else {
+            StateResult::Continue
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Ok(None)
+    }
+
+    fn between_tokens(&self) -> bool {
+        true
+    }
+
+    fn trivia(&self) -> TriviaKind {
+        TriviaKind::Comment
+    }
+}
+
+impl BlockComment {
+    pub fn new() -> BlockComment {
+        BlockComment { depth: 1, last: None }
+    }
+}
+
+impl State for BlockComment {
+    fn lex(&mut self, c: char) -> StateResult {
+        match (self.last, c) {
+            (Some('|'), '#') => {
+                self.depth -= 1;
+                if self.depth == 0 {
+                    return StateResult::Discard(Resume::AtNext);
+                }
+                self.last = None;
+            },
+            (Some('#'), '|') => {
+                self.depth += 1;
+                self.last = None;
+            },
+            _ => self.last = Some(c),
+        }
+        StateResult::Continue
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Err(Error::new(ErrorKind::UnexpectedEof, "unterminated block comment".to_string()))
+    }
+
+    fn trivia(&self) -> TriviaKind {
+        TriviaKind::Comment
+    }
+}
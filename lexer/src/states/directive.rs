@@ -0,0 +1,49 @@
+/* lexer/src/states/directive.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use chars::Lexable;
+use error::Error;
+use states::{State, StateResult};
+use token::{Token, TriviaKind};
+
+/// A `#!` directive, like `#!fold-case`. Holds the name of the directive so far.
+#[derive(Debug)] pub struct Directive(String);
+
+impl Directive {
+    pub fn new() -> Directive {
+        Directive(String::new())
+    }
+
+    /// Whether the directive turns case folding on or off.
+    fn fold_case(&self) -> Result<bool, Error> {
+        match self.0.as_str() {
+            "fold-case" => Ok(true),
+            "no-fold-case" => Ok(false),
+            name => Err(Error::unknown_directive(name)),
+        }
+    }
+}
+
+impl State for Directive {
+    fn lex(&mut self, c: char) -> StateResult {
+        if c.is_identifier_delimiter() {
+            match self.fold_case() {
+                Ok(fold) => StateResult::FoldCase(fold),
+                Err(err) => StateResult::fail(err),
+            }
+        } else {
+            self.0.push(c);
+            StateResult::Continue
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        // There's nothing left to fold, so the directive is just discarded.
+        self.fold_case().map(|_| None)
+    }
+
+    fn trivia(&self) -> TriviaKind {
+        TriviaKind::Comment
+    }
+}
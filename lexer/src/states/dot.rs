@@ -5,7 +5,8 @@
 use chars::Lexable;
 use error::Error;
 use states::{Resume, State, StateResult};
-use states::number::{Builder, Digit};
+use states::id::IdSub;
+use states::number::{Builder, Decimal};
 use token::Token;
 
 #[derive(Debug)] pub struct Dot;
@@ -20,8 +21,10 @@ impl State for Dot {
     fn lex(&mut self, c: char) -> StateResult {
         if c.is_identifier_delimiter() {
             StateResult::emit(Token::Dot, Resume::Here)
-        } else if let Some(st) = Digit::with_char(&Builder::new(), c) {
+        } else if let Some(st) = Decimal::with_char(&Builder::new(), c) {
             StateResult::advance(Box::new(st))
+        } else if c.is_dot_subsequent() {
+            StateResult::advance(Box::new(IdSub::new(format!(".{}", c))))
         } else {
             StateResult::fail(Error::invalid_char(c))
         }
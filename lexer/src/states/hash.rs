@@ -3,10 +3,15 @@
  */
 
 use chars::Lexable;
-use error::Error;
-use states::{State, StateResult};
+use error::{Error, ErrorKind};
+use states::{Resume, State, StateResult};
 use states::bool::Bool;
+use states::character::Char;
+use states::comment::BlockComment;
+use states::directive::Directive;
+use states::label::Label;
 use states::number::{Builder, Prefix};
+use states::vector::ByteVector;
 use token::Token;
 
 trait HashLexable {
@@ -14,10 +19,11 @@ trait HashLexable {
     fn is_slash(&self) -> bool;
 }
 
-#[derive(Debug)] pub struct Hash;
+/// A `#`, which starts all kinds of things. Holds whether case folding is on.
+#[derive(Debug)] pub struct Hash(bool);
 
 impl Hash {
-    pub fn new() -> Hash { Hash{} }
+    pub fn new(fold: bool) -> Hash { Hash(fold) }
 }
 
 impl State for Hash {
@@ -27,11 +33,18 @@ impl State for Hash {
                 let buf = c.to_ascii_lowercase().to_string();
                 StateResult::advance(Box::new(Bool::new(buf.as_str())))
             },
+            c if c.is_slash() => StateResult::advance(Box::new(Char::new(self.0))),
+            '|' => StateResult::advance(Box::new(BlockComment::new())),
+            '!' => StateResult::advance(Box::new(Directive::new())),
+            ';' => StateResult::emit(Token::DatumComment, Resume::AtNext),
+            c if c.is_ascii_digit() => StateResult::advance(Box::new(Label::with_char(c))),
+            '(' => StateResult::emit(Token::VectorStart, Resume::AtNext),
+            'u' | 'U' => StateResult::advance(Box::new(ByteVector::new())),
             c if c.is_radix() || c.is_exactness() => {
                 if let Some(st) = Prefix::with_char(&Builder::new(), c) {
                     StateResult::advance(Box::new(st))
                 } else {
-                    StateResult::fail(Error::new(format!("invalid numeric prefix character: {}", c)))
+                    StateResult::fail(Error::new(ErrorKind::BadNumber, format!("invalid numeric prefix character: {}", c)))
                 }
             },
             _ => StateResult::fail(Error::invalid_char(c)),
@@ -2,24 +2,42 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
+use std::mem;
 use chars::Lexable;
 use error::Error;
 use states::{Resume, State, StateResult};
 use token::Token;
 
+/// The rest of an identifier, once its start has been seen. Holds the identifier so far.
 #[derive(Debug)]
-pub struct IdSub;
+pub struct IdSub(String);
+
+impl IdSub {
+    pub fn new(value: String) -> IdSub {
+        IdSub(value)
+    }
+
+    pub fn with_char(c: char) -> IdSub {
+        IdSub(c.to_string())
+    }
+}
 
 impl State for IdSub {
     fn lex(&mut self, c: char) -> StateResult {
         match c {
-            c if c.is_identifier_subsequent() => StateResult::Continue,
-            c if c.is_identifier_delimiter() => StateResult::Emit(Token::Id, Resume::Here),
+            c if c.is_identifier_subsequent() => {
+                self.0.push(c);
+                StateResult::Continue
+            },
+            c if c.is_identifier_delimiter() => {
+                let value = mem::take(&mut self.0);
+                StateResult::Emit(Token::Id(value), Resume::Here)
+            },
             _ => StateResult::fail(Error::invalid_char(c)),
         }
     }
 
     fn none(&mut self) -> Result<Option<Token>, Error> {
-        Ok(Some(Token::Id))
+        Ok(Some(Token::Id(mem::take(&mut self.0))))
     }
 }
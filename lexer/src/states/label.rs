@@ -0,0 +1,48 @@
+/* lexer/src/states/label.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use error::{Error, ErrorKind};
+use states::{Resume, State, StateResult};
+use token::Token;
+
+/// A `#` followed by digits, which is the start of a `#n=` datum label or a `#n#` reference.
+/// Holds the digits so far.
+#[derive(Debug)] pub struct Label(String);
+
+impl Label {
+    pub fn with_char(c: char) -> Label {
+        Label(c.to_string())
+    }
+
+    fn number(&self) -> Result<usize, Error> {
+        self.0.parse().map_err(|_| {
+            Error::new(ErrorKind::BadLabel, format!("datum label out of range: {}", self.0))
+        })
+    }
+
+    fn emit(&self, token: fn(usize) -> Token) -> StateResult {
+        match self.number() {
+            Ok(n) => StateResult::emit(token(n), Resume::AtNext),
+            Err(err) => StateResult::fail(err),
+        }
+    }
+}
+
+impl State for Label {
+    fn lex(&mut self, c: char) -> StateResult {
+        match c {
+            c if c.is_ascii_digit() => {
+                self.0.push(c);
+                StateResult::Continue
+            },
+            '=' => self.emit(Token::DatumLabel),
+            '#' => self.emit(Token::DatumRef),
+            _ => StateResult::fail(Error::invalid_char(c)),
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Err(Error::unexpected_eof())
+    }
+}
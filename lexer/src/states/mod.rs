@@ -2,20 +2,31 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
+use std::any::type_name;
 use std::fmt::Debug;
 use error::Error;
-use token::Token;
+use token::{Token, TriviaKind};
 
 mod begin;
 mod bool;
+mod character;
+mod comma;
+mod comment;
+mod directive;
 mod dot;
 mod hash;
 mod number;
 mod id;
+mod label;
+mod recover;
+mod string;
+mod vector;
 mod whitespace;
 
 pub use self::begin::Begin;
 
+use self::recover::Skip;
+
 #[derive(Debug)]
 pub enum StateResult {
     /// Consume the character, remain on this state.
@@ -27,6 +38,9 @@ pub enum StateResult {
     /// Emit a Lex with the provided Token and the accumulated buffer. The Resume value indicates
     /// whether to revisit the current input character or advance to the next one.
     Emit(Token, Resume),
+    /// Turn case folding on or off for the rest of the input, and discard the input consumed to
+    /// this point, a `#!fold-case` or `#!no-fold-case` directive. Resumes at the current character.
+    FoldCase(bool),
     Fail(Error)
 }
 
@@ -38,9 +52,42 @@ pub enum Resume {
     AtNext
 } 
 
-pub trait State: Debug {
+pub trait State: Debug + Named {
     fn lex(&mut self, c: char) -> StateResult;
     fn none(&mut self) -> Result<Option<Token>, Error>;
+
+    /// Whether the input can stop in this state without leaving a token unfinished. A resumable
+    /// `Lexer` that runs out of input in any other state asks for more.
+    fn between_tokens(&self) -> bool {
+        false
+    }
+
+    /// What the input this state discards is, for a `Lexer` that keeps trivia. Most states don't
+    /// discard anything but the remains of a bad token.
+    fn trivia(&self) -> TriviaKind {
+        TriviaKind::Skipped
+    }
+
+    /// Called when `lex` fails. Returns the state that skips the rest of the bad token, so lexing
+    /// can pick up again after it. The character that caused the failure is handed to that state
+    /// next.
+    fn recover(&self) -> Box<State> {
+        Box::new(Skip::new())
+    }
+}
+
+/// Provides the name of a state for tracing. This is implemented for every state, so there's no
+/// need to implement it by hand. The name is the type's name without its module, so no two states
+/// can share a type name, or they'd be one node in the state graph.
+pub trait Named {
+    fn name(&self) -> &'static str;
+}
+
+impl<T> Named for T where T: State {
+    fn name(&self) -> &'static str {
+        let name = type_name::<T>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
 }
 
 impl StateResult {
@@ -0,0 +1,65 @@
+/* lexer/src/states/number/complex.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use chars::Lexable;
+use error::Error;
+use states::{Resume, State, StateResult};
+use states::number::Builder;
+use states::number::decimal::Decimal;
+use states::number::digit::Digit;
+use states::number::sign::Sign;
+use token::Token;
+
+/// The `i` at the end of the imaginary part of a rectangular complex number.
+#[derive(Debug)] pub struct Imaginary(Builder);
+
+/// The angle of a polar complex number, after the `@`.
+#[derive(Debug)] pub struct Angle(Builder);
+
+impl Imaginary {
+    pub fn new(b: Builder) -> Imaginary {
+        Imaginary(b)
+    }
+}
+
+impl State for Imaginary {
+    fn lex(&mut self, c: char) -> StateResult {
+        if c.is_identifier_delimiter() {
+            match self.0.resolve_imaginary() {
+                Ok(token) => StateResult::emit(token, Resume::Here),
+                Err(err) => StateResult::fail(err),
+            }
+        } else {
+            StateResult::fail(Error::invalid_char(c))
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        self.0.resolve_imaginary().map(Some)
+    }
+}
+
+impl Angle {
+    pub fn new(b: Builder) -> Angle {
+        Angle(b)
+    }
+}
+
+impl State for Angle {
+    fn lex(&mut self, c: char) -> StateResult {
+        if let Some(st) = Sign::with_char(&self.0, c) {
+            StateResult::advance(Box::new(st))
+        } else if let Some(st) = Digit::with_char(&self.0, c) {
+            StateResult::advance(Box::new(st))
+        } else if c.is_dot() && self.0.push_decimal_point().is_ok() {
+            StateResult::advance(Box::new(Decimal::new(self.0.clone())))
+        } else {
+            StateResult::fail(Error::invalid_char(c))
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Err(Error::unexpected_eof())
+    }
+}
@@ -0,0 +1,48 @@
+/* lexer/src/states/number/decimal.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use chars::Lexable;
+use error::Error;
+use states::{State, StateResult};
+use states::number::{complex_part, emit, finish, Builder};
+use states::number::exponent::Exponent;
+use token::Token;
+
+/// The fractional part of a decimal number, after the decimal point.
+#[derive(Debug)] pub struct Decimal(Builder);
+
+impl Decimal {
+    pub fn new(b: Builder) -> Decimal {
+        Decimal(b)
+    }
+
+    /// Start a decimal with a leading decimal point, e.g. `.5`, with `c` as its first digit.
+    pub fn with_char(b: &Builder, c: char) -> Option<Decimal> {
+        let mut b = b.clone();
+        if b.push_decimal_point().is_err() || b.push_fraction_digit(c).is_err() {
+            return None;
+        }
+        Some(Decimal::new(b))
+    }
+}
+
+impl State for Decimal {
+    fn lex(&mut self, c: char) -> StateResult {
+        if self.0.push_fraction_digit(c).is_ok() {
+            StateResult::Continue
+        } else if c.is_exponent_marker() && self.0.push_exponent_marker().is_ok() {
+            StateResult::advance(Box::new(Exponent::new(self.0.clone())))
+        } else if let Some(result) = complex_part(&self.0, c) {
+            result
+        } else if c.is_identifier_delimiter() {
+            emit(&self.0)
+        } else {
+            StateResult::fail(Error::invalid_char(c))
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        finish(&self.0)
+    }
+}
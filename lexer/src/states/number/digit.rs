@@ -4,8 +4,11 @@
 
 use chars::Lexable;
 use error::Error;
-use states::{State, StateResult, Resume};
-use states::number::{Builder, Radix};
+use states::{State, StateResult};
+use states::number::{complex_part, emit, finish, Builder, Radix};
+use states::number::decimal::Decimal;
+use states::number::exponent::Exponent;
+use states::number::ratio::Ratio;
 use token::Token;
 
 #[derive(Debug)] pub struct Digit(Builder);
@@ -32,14 +35,23 @@ impl State for Digit {
     fn lex(&mut self, c: char) -> StateResult {
         if self.0.push_digit(c).is_ok() {
             StateResult::Continue
+        } else if c.is_dot() && self.0.push_decimal_point().is_ok() {
+            StateResult::advance(Box::new(Decimal::new(self.0.clone())))
+        } else if c == '/' {
+            self.0.push_slash();
+            StateResult::advance(Box::new(Ratio::new(self.0.clone())))
+        } else if c.is_exponent_marker() && self.0.push_exponent_marker().is_ok() {
+            StateResult::advance(Box::new(Exponent::new(self.0.clone())))
+        } else if let Some(result) = complex_part(&self.0, c) {
+            result
         } else if c.is_identifier_delimiter() {
-            StateResult::emit(Token::Num(self.0.resolve()), Resume::Here)
+            emit(&self.0)
         } else {
             StateResult::fail(Error::invalid_char(c))
         }
     }
 
     fn none(&mut self) -> Result<Option<Token>, Error> {
-        Ok(Some(Token::Num(self.0.resolve())))
+        finish(&self.0)
     }
 }
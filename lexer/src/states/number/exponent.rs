@@ -0,0 +1,40 @@
+/* lexer/src/states/number/exponent.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use chars::Lexable;
+use error::Error;
+use states::{State, StateResult};
+use states::number::{complex_part, emit, finish, Builder, Sign};
+use token::Token;
+
+/// The exponent of a decimal number, after the exponent marker.
+#[derive(Debug)] pub struct Exponent(Builder);
+
+impl Exponent {
+    pub fn new(b: Builder) -> Exponent {
+        Exponent(b)
+    }
+}
+
+impl State for Exponent {
+    fn lex(&mut self, c: char) -> StateResult {
+        let pushed = match Sign::from(c) {
+            Some(sign) => self.0.push_exponent_sign(sign),
+            None => self.0.push_exponent_digit(c),
+        };
+        if pushed.is_ok() {
+            StateResult::Continue
+        } else if let Some(result) = complex_part(&self.0, c) {
+            result
+        } else if c.is_identifier_delimiter() {
+            emit(&self.0)
+        } else {
+            StateResult::fail(Error::invalid_char(c))
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        finish(&self.0)
+    }
+}
@@ -0,0 +1,111 @@
+/* lexer/src/states/number/infnan.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use std::f64;
+use chars::Lexable;
+use error::Error;
+use states::{Resume, State, StateResult};
+use states::id::IdSub;
+use states::number::{complex_part, emit, finish, Builder};
+use states::number::sign::could_be_identifier;
+use token::Token;
+
+const INF: &str = "inf.0";
+const NAN: &str = "nan.0";
+
+/// Letters following a sign, which could be `+inf.0`, `-nan.0`, the imaginary unit `+i`, or,
+/// without a prefix, an identifier like `+inc` or `-nope`.
+#[derive(Debug)]
+pub struct InfNan {
+    b: Builder,
+    /// The letters seen so far, lowercased.
+    text: String,
+    /// The letters seen so far, as written.
+    raw: String,
+}
+
+impl InfNan {
+    pub fn with_char(b: &Builder, c: char) -> Option<InfNan> {
+        let mut st = InfNan { b: b.clone(), text: String::new(), raw: String::new() };
+        if st.push(c) { Some(st) } else { None }
+    }
+
+    /// Add `c` to the text if it continues one of the special values. Returns false if it
+    /// doesn't.
+    fn push(&mut self, c: char) -> bool {
+        let mut text = self.text.clone();
+        text.extend(c.to_lowercase());
+        if !INF.starts_with(&text) && !NAN.starts_with(&text) {
+            return false;
+        }
+        self.text = text;
+        self.raw.push(c);
+        true
+    }
+
+    fn is_complete(&self) -> bool {
+        self.text == INF || self.text == NAN
+    }
+
+    fn is_imaginary_unit(&self) -> bool {
+        self.text == "i"
+    }
+
+    fn identifier(&self) -> String {
+        format!("{}{}", self.b.sign_char(), self.raw)
+    }
+
+    fn imaginary_unit(&self) -> Result<Token, Error> {
+        let mut b = self.b.clone();
+        b.push_digit('1')?;
+        b.resolve_imaginary()
+    }
+}
+
+impl State for InfNan {
+    fn lex(&mut self, c: char) -> StateResult {
+        if self.is_complete() {
+            if let Some(result) = complex_part(&self.b, c) {
+                return result;
+            } else if c.is_identifier_delimiter() {
+                return emit(&self.b);
+            }
+        } else if self.is_imaginary_unit() && c.is_identifier_delimiter() {
+            return match self.imaginary_unit() {
+                Ok(token) => StateResult::emit(token, Resume::Here),
+                Err(err) => StateResult::fail(err),
+            };
+        } else if self.push(c) {
+            if self.is_complete() {
+                let value = if self.text == INF { f64::INFINITY } else { f64::NAN };
+                if let Err(err) = self.b.push_infnan(value) {
+                    return StateResult::fail(err);
+                }
+            }
+            return StateResult::Continue;
+        }
+
+        if !could_be_identifier(&self.b) {
+            StateResult::fail(Error::invalid_char(c))
+        } else if c.is_identifier_delimiter() {
+            StateResult::emit(Token::Id(self.identifier()), Resume::Here)
+        } else if c.is_identifier_subsequent() {
+            StateResult::advance(Box::new(IdSub::new(format!("{}{}", self.identifier(), c))))
+        } else {
+            StateResult::fail(Error::invalid_char(c))
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        if self.is_complete() {
+            finish(&self.b)
+        } else if self.is_imaginary_unit() {
+            self.imaginary_unit().map(Some)
+        } else if could_be_identifier(&self.b) {
+            Ok(Some(Token::Id(self.identifier())))
+        } else {
+            Err(Error::unexpected_eof())
+        }
+    }
+}
@@ -2,12 +2,21 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
+use sibiltypes::{Frac, Int, Irr, Number};
 use error::Error;
+use states::{Resume, StateResult};
+use token::{Num, Token};
 
+mod complex;
+mod decimal;
 mod digit;
+mod exponent;
+mod infnan;
 mod prefix;
-mod sign;
+mod ratio;
+pub mod sign;
 
+pub use self::decimal::Decimal;
 pub use self::prefix::Prefix;
 pub use self::digit::Digit;
 
@@ -20,12 +29,32 @@ pub enum Sign { Neg = -1, Pos = 1 }
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Exact { Yes, No }
 
+/// Accumulates the pieces of a number literal as the number states lex it. Digits are kept as
+/// strings until the whole literal has been seen, because exactness and the presence of a
+/// decimal point or exponent can change how they are interpreted.
 #[derive(Clone, Debug)]
 pub struct Builder {
     radix: Option<Radix>,
     sign: Option<Sign>,
     exact: Option<Exact>,
-    value: i64,
+    /// Digits of the integer part of a decimal, or of the numerator of a ratio.
+    whole: String,
+    /// Digits following the decimal point, if there was one.
+    fraction: Option<String>,
+    /// Digits of the denominator, if this is a ratio.
+    denominator: Option<String>,
+    /// Sign of the exponent, if there was one.
+    exponent_sign: Option<Sign>,
+    /// Digits of the exponent, if there was an exponent marker.
+    exponent: Option<String>,
+    /// Value of an infinity or NaN, if the current part is `+inf.0`, `-inf.0`, `+nan.0`, or
+    /// `-nan.0`.
+    special: Option<f64>,
+    /// The first part of a complex number: the real part of a rectangular number, or the
+    /// magnitude of a polar one. The fields above then describe the second part.
+    first: Option<Num>,
+    /// True if the first part was followed by `@`.
+    polar: bool,
 }
 
 impl Radix {
@@ -40,6 +69,16 @@ impl Radix {
     }
 }
 
+impl Sign {
+    pub fn from(c: char) -> Option<Sign> {
+        match c {
+            '+' => Some(Sign::Pos),
+            '-' => Some(Sign::Neg),
+            _ => None
+        }
+    }
+}
+
 impl Exact {
     pub fn from(c: char) -> Option<Exact> {
         match c {
@@ -56,18 +95,86 @@ impl Builder {
              radix: None,
              sign: None,
              exact: None,
-             value: 0,
+             whole: String::new(),
+             fraction: None,
+             denominator: None,
+             exponent_sign: None,
+             exponent: None,
+             special: None,
+             first: None,
+             polar: false,
         }
     }
 
     fn push_digit(&mut self, c: char) -> Result<(), Error> {
+        if c.is_digit(self.radix_value()) {
+            self.whole.push(c);
+            Ok(())
+        } else {
+            Err(Error::invalid_char(c))
+        }
+    }
+
+    /// Start the fractional part of a decimal. Decimals are only allowed in base 10.
+    fn push_decimal_point(&mut self) -> Result<(), Error> {
+        if self.radix_value() != 10 || self.fraction.is_some() {
+            return Err(Error::invalid_char('.'));
+        }
+        self.fraction = Some(String::new());
+        Ok(())
+    }
+
+    fn push_fraction_digit(&mut self, c: char) -> Result<(), Error> {
+        match self.fraction {
+            Some(ref mut fraction) if c.is_ascii_digit() => {
+                fraction.push(c);
+                Ok(())
+            },
+            _ => Err(Error::invalid_char(c))
+        }
+    }
+
+    fn push_slash(&mut self) {
+        self.denominator = Some(String::new());
+    }
+
+    fn push_denominator_digit(&mut self, c: char) -> Result<(), Error> {
         let rx = self.radix_value();
-        match c.to_digit(rx as u32) {
-            Some(d) => {
-                self.value = self.value * rx as i64 + d as i64;
+        match self.denominator {
+            Some(ref mut denominator) if c.is_digit(rx) => {
+                denominator.push(c);
+                Ok(())
+            },
+            _ => Err(Error::invalid_char(c))
+        }
+    }
+
+    /// Start an exponent. Exponents are only allowed in base 10.
+    fn push_exponent_marker(&mut self) -> Result<(), Error> {
+        if self.radix_value() != 10 || self.exponent.is_some() {
+            return Err(Error::invalid_char('e'));
+        }
+        self.exponent = Some(String::new());
+        Ok(())
+    }
+
+    fn push_exponent_sign(&mut self, sign: Sign) -> Result<(), Error> {
+        match self.exponent {
+            Some(ref exponent) if exponent.is_empty() && self.exponent_sign.is_none() => {
+                self.exponent_sign = Some(sign);
                 Ok(())
             },
-            None => Err(Error::invalid_char(c))
+            _ => Err(Error::invalid_char(if sign == Sign::Neg { '-' } else { '+' }))
+        }
+    }
+
+    fn push_exponent_digit(&mut self, c: char) -> Result<(), Error> {
+        match self.exponent {
+            Some(ref mut exponent) if c.is_ascii_digit() => {
+                exponent.push(c);
+                Ok(())
+            },
+            _ => Err(Error::invalid_char(c))
         }
     }
 
@@ -83,15 +190,268 @@ impl Builder {
         self.sign = Some(sign);
     }
 
-    fn resolve(&self) -> i64 {
-        let sign_factor = self.sign_value() as i64;
-        self.value * sign_factor
+    fn push_infnan(&mut self, value: f64) -> Result<(), Error> {
+        if self.exact == Some(Exact::Yes) {
+            return Err(Error::bad_number("infinities and NaNs are inexact"));
+        }
+        self.special = Some(value * self.sign_value() as f64);
+        Ok(())
+    }
+
+    /// Finish the real part of a rectangular complex number, and start the imaginary part with
+    /// the sign `sign`.
+    fn push_imaginary_sign(&mut self, sign: Sign) -> Result<(), Error> {
+        if self.first.is_some() {
+            return Err(Error::invalid_char(if sign == Sign::Neg { '-' } else { '+' }));
+        }
+        self.start_second_part()?;
+        self.sign = Some(sign);
+        Ok(())
+    }
+
+    /// Finish the magnitude of a polar complex number and start its angle.
+    fn push_at(&mut self) -> Result<(), Error> {
+        if self.first.is_some() {
+            return Err(Error::invalid_char('@'));
+        }
+        self.start_second_part()?;
+        self.polar = true;
+        Ok(())
+    }
+
+    fn start_second_part(&mut self) -> Result<(), Error> {
+        self.first = Some(self.resolve_part()?);
+        self.sign = None;
+        self.whole.clear();
+        self.fraction = None;
+        self.denominator = None;
+        self.exponent_sign = None;
+        self.exponent = None;
+        self.special = None;
+        Ok(())
+    }
+
+    /// Produce a real or polar number from the accumulated pieces of the literal.
+    fn resolve(&self) -> Result<Token, Error> {
+        let part = self.resolve_part()?;
+        match self.first {
+            Some(magnitude) if self.polar => Builder::polar(magnitude, part, self.exact),
+            Some(_) => Err(Error::bad_number("imaginary part is missing an i")),
+            None => Ok(Token::Num(part)),
+        }
+    }
+
+    /// Produce a complex number whose imaginary part is the current part of the literal. Its real
+    /// part is the first part, if there was one.
+    fn resolve_imaginary(&self) -> Result<Token, Error> {
+        if self.polar || !self.seen_sign() {
+            return Err(Error::invalid_char('i'));
+        }
+        let imag = self.resolve_part()?;
+        let real = self.first.unwrap_or(Num::Int(Int(0)));
+        if Builder::is_exact_zero(&imag) {
+            Ok(Token::Num(real))
+        } else {
+            Ok(Token::Complex(real, imag))
+        }
+    }
+
+    fn polar(magnitude: Num, angle: Num, exact: Option<Exact>) -> Result<Token, Error> {
+        if Builder::is_exact_zero(&angle) {
+            return Ok(Token::Num(magnitude));
+        }
+        if exact == Some(Exact::Yes) {
+            return Err(Error::bad_number("polar numbers are inexact"));
+        }
+        let (m, a) = (Builder::to_float(&magnitude), Builder::to_float(&angle));
+        Ok(Token::Complex(Num::Irr(Irr(m * a.cos())), Num::Irr(Irr(m * a.sin()))))
+    }
+
+    fn is_exact_zero(num: &Num) -> bool {
+        matches!(*num, Num::Int(Int(0)))
+    }
+
+    fn to_float(num: &Num) -> f64 {
+        match *num {
+            Num::Int(Int(i)) => i as f64,
+            Num::Frac(ref f) => f.quotient(),
+            Num::Irr(Irr(f)) => f,
+        }
+    }
+
+    /// Produce a number from the accumulated pieces of the current part of the literal.
+    fn resolve_part(&self) -> Result<Num, Error> {
+        if let Some(value) = self.special {
+            Ok(Num::Irr(Irr(value)))
+        } else if self.denominator.is_some() {
+            self.resolve_ratio()
+        } else if self.fraction.is_some() || self.exponent.is_some() {
+            self.resolve_decimal()
+        } else {
+            self.resolve_integer()
+        }
+    }
+
+    fn resolve_integer(&self) -> Result<Num, Error> {
+        if self.whole.is_empty() {
+            return Err(Error::bad_number("no digits"));
+        }
+        match self.exact {
+            Some(Exact::No) => Ok(Num::Irr(Irr(self.signed_float(&self.whole)))),
+            _ => self.signed_int(&self.whole).map(|i| Num::Int(Int(i)))
+        }
+    }
+
+    fn resolve_ratio(&self) -> Result<Num, Error> {
+        let denominator = self.denominator.as_deref().unwrap_or("");
+        if self.whole.is_empty() || denominator.is_empty() {
+            return Err(Error::bad_number("ratio is missing digits"));
+        }
+        match self.exact {
+            Some(Exact::No) => {
+                let p = self.signed_float(&self.whole);
+                let q = self.float(denominator);
+                Ok(Num::Irr(Irr(p / q)))
+            },
+            _ => {
+                let p = self.signed_int(&self.whole)?;
+                let q = self.int(denominator)?;
+                Builder::exact_ratio(p, q)
+            },
+        }
+    }
+
+    fn resolve_decimal(&self) -> Result<Num, Error> {
+        let fraction = self.fraction.as_deref().unwrap_or("");
+        if self.whole.is_empty() && fraction.is_empty() {
+            return Err(Error::bad_number("no digits"));
+        }
+        let exponent = match self.exponent {
+            Some(ref exponent) if exponent.is_empty() => {
+                return Err(Error::bad_number("exponent is missing digits"));
+            },
+            Some(ref exponent) => {
+                let sign = if self.exponent_sign == Some(Sign::Neg) { "-" } else { "" };
+                format!("{}{}", sign, exponent).parse::<i32>()
+                    .map_err(|_| Error::bad_number("exponent is too large"))?
+            },
+            None => 0,
+        };
+
+        match self.exact {
+            Some(Exact::Yes) => {
+                // Shift the decimal point all the way to the right, and then account for it with
+                // the exponent.
+                let digits = format!("{}{}", self.whole, fraction);
+                let mantissa = self.signed_int(&digits)?;
+                let scale = exponent - fraction.len() as i32;
+                let power = Builder::power_of_ten(scale.abs())?;
+                if scale >= 0 {
+                    match mantissa.checked_mul(power) {
+                        Some(value) => Ok(Num::Int(Int(value))),
+                        None => Err(Builder::too_large()),
+                    }
+                } else {
+                    Builder::exact_ratio(mantissa, power)
+                }
+            },
+            _ => {
+                let sign = if self.sign_value() < 0 { "-" } else { "" };
+                let whole = if self.whole.is_empty() { "0" } else { &self.whole };
+                let fraction = if fraction.is_empty() { "0" } else { fraction };
+                let value = format!("{}{}.{}e{}", sign, whole, fraction, exponent);
+                value.parse::<f64>()
+                     .map(|f| Num::Irr(Irr(f)))
+                     .map_err(|_| Error::bad_number(&value))
+            },
+        }
+    }
+
+    fn exact_ratio(p: i64, q: i64) -> Result<Num, Error> {
+        if q == 0 {
+            return Err(Error::bad_number("division by zero"));
+        }
+        // Frac reduces in wider math than i64, and refuses fractions that don't fit.
+        match Frac::from_ints(p, q) {
+            Some(frac) => match frac.as_int() {
+                Some(int) => Ok(Num::Int(int)),
+                None => Ok(Num::Frac(frac)),
+            },
+            None => Err(Builder::too_large()),
+        }
+    }
+
+    fn power_of_ten(exponent: i32) -> Result<i64, Error> {
+        10i64.checked_pow(exponent as u32).ok_or_else(Builder::too_large)
+    }
+
+    fn int(&self, digits: &str) -> Result<i64, Error> {
+        i64::from_str_radix(digits, self.radix_value()).map_err(|_| Builder::too_large())
+    }
+
+    fn signed_int(&self, digits: &str) -> Result<i64, Error> {
+        // Parse with the sign attached so the most negative i64 can be read.
+        let sign = if self.sign_value() < 0 { "-" } else { "" };
+        let digits = format!("{}{}", sign, digits);
+        i64::from_str_radix(&digits, self.radix_value()).map_err(|_| Builder::too_large())
+    }
+
+    fn float(&self, digits: &str) -> f64 {
+        let rx = self.radix_value() as f64;
+        digits.chars()
+              .filter_map(|c| c.to_digit(self.radix_value()))
+              .fold(0.0, |acc, d| acc * rx + d as f64)
+    }
+
+    fn signed_float(&self, digits: &str) -> f64 {
+        self.float(digits) * self.sign_value() as f64
+    }
+
+    fn too_large() -> Error {
+        Error::bad_number("too large")
     }
 
     fn seen_exact(&self) -> bool { self.exact.is_some() }
     fn seen_radix(&self) -> bool { self.radix.is_some() }
     fn seen_sign(&self) -> bool { self.sign.is_some() }
+    fn seen_prefix(&self) -> bool { self.seen_exact() || self.seen_radix() }
+    fn seen_first(&self) -> bool { self.first.is_some() }
+
+    fn radix_value(&self) -> u32 { self.radix.unwrap_or(Radix::Dec) as u32 }
+    fn sign_value(&self) -> i8 { self.sign.unwrap_or(Sign::Pos) as i8 }
+    fn sign_char(&self) -> char { if self.sign == Some(Sign::Neg) { '-' } else { '+' } }
+}
 
-    fn radix_value(&self) -> u8 { self.radix.unwrap_or(Radix::Dec) as u8 }
-    fn sign_value(&self) -> u8 { self.sign.unwrap_or(Sign::Pos) as u8 }
+/// Emit the number accumulated by `b`, revisiting the current character in the next state.
+fn emit(b: &Builder) -> StateResult {
+    match b.resolve() {
+        Ok(token) => StateResult::emit(token, Resume::Here),
+        Err(err) => StateResult::fail(err),
+    }
+}
+
+/// Finish the number accumulated by `b` at the end of input.
+fn finish(b: &Builder) -> Result<Option<Token>, Error> {
+    b.resolve().map(Some)
+}
+
+/// Continue a number after its digits with `c`, which may start the second part of a complex
+/// number. Returns None if `c` can't do that.
+fn complex_part(b: &Builder, c: char) -> Option<StateResult> {
+    let mut b = b.clone();
+    if let Some(sign) = Sign::from(c) {
+        Some(match b.push_imaginary_sign(sign) {
+            Ok(_) => StateResult::advance(Box::new(sign::Sign::new(b))),
+            Err(err) => StateResult::fail(err),
+        })
+    } else if c == '@' {
+        Some(match b.push_at() {
+            Ok(_) => StateResult::advance(Box::new(complex::Angle::new(b))),
+            Err(err) => StateResult::fail(err),
+        })
+    } else if c == 'i' || c == 'I' {
+        Some(StateResult::advance(Box::new(complex::Imaginary::new(b))))
+    } else {
+        None
+    }
 }
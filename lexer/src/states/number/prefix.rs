@@ -6,12 +6,13 @@ use chars::Lexable;
 use error::Error;
 use states::{State, StateResult};
 use states::number::{Builder, Radix, Exact};
+use states::number::decimal::Decimal;
 use states::number::digit::Digit;
 use states::number::sign::Sign;
 use token::Token;
 
 #[derive(Debug)] pub struct Prefix(Builder);
-#[derive(Debug)] pub struct Hash(Builder);
+#[derive(Debug)] pub struct PrefixHash(Builder);
 
 impl Prefix {
     pub fn new(b: Builder) -> Prefix {
@@ -42,11 +43,13 @@ impl Prefix {
 impl State for Prefix {
     fn lex(&mut self, c: char) -> StateResult {
         if c.is_hash() {
-            StateResult::advance(Box::new(Hash::new(&self.0)))
+            StateResult::advance(Box::new(PrefixHash::new(&self.0)))
         } else if let Some(st) = Sign::with_char(&self.0, c) {
             StateResult::advance(Box::new(st))
         } else if let Some(st) = Digit::with_char(&self.0, c) {
             StateResult::advance(Box::new(st))
+        } else if c.is_dot() && self.0.push_decimal_point().is_ok() {
+            StateResult::advance(Box::new(Decimal::new(self.0.clone())))
         } else {
             StateResult::fail(Error::invalid_char(c))
         }
@@ -57,13 +60,13 @@ impl State for Prefix {
     }
 }
 
-impl Hash {
-    fn new(b: &Builder) -> Hash {
-        Hash(b.clone())
+impl PrefixHash {
+    fn new(b: &Builder) -> PrefixHash {
+        PrefixHash(b.clone())
     }
 }
 
-impl State for Hash {
+impl State for PrefixHash {
     fn lex(&mut self, c: char) -> StateResult {
         if let Some(st) = Prefix::with_char(&self.0, c) {
             StateResult::advance(Box::new(st))
@@ -73,6 +76,6 @@ impl State for Hash {
     }
 
     fn none(&mut self) -> Result<Option<Token>, Error> {
-        Err(Error::new("blah".to_string()))
+        Err(Error::unexpected_eof())
     }
 }
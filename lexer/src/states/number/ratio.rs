@@ -0,0 +1,36 @@
+/* lexer/src/states/number/ratio.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use chars::Lexable;
+use error::Error;
+use states::{State, StateResult};
+use states::number::{complex_part, emit, finish, Builder};
+use token::Token;
+
+/// The denominator of a ratio, after the `/`.
+#[derive(Debug)] pub struct Ratio(Builder);
+
+impl Ratio {
+    pub fn new(b: Builder) -> Ratio {
+        Ratio(b)
+    }
+}
+
+impl State for Ratio {
+    fn lex(&mut self, c: char) -> StateResult {
+        if self.0.push_denominator_digit(c).is_ok() {
+            StateResult::Continue
+        } else if let Some(result) = complex_part(&self.0, c) {
+            result
+        } else if c.is_identifier_delimiter() {
+            emit(&self.0)
+        } else {
+            StateResult::fail(Error::invalid_char(c))
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        finish(&self.0)
+    }
+}
@@ -2,46 +2,88 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
+use chars::Lexable;
 use error::Error;
-use states::{State, StateResult};
+use states::{Resume, State, StateResult};
+use states::id::IdSub;
 use states::number::Builder;
 use states::number::Sign as Sgn;
+use states::number::decimal::Decimal;
+use states::number::digit::Digit;
+use states::number::infnan::InfNan;
 use token::Token;
 
+/// A sign at the start of a number or of the imaginary part of a complex number. Without a prefix,
+/// a sign at the start could also be the start of an identifier like `+`, `-`, or `->x`.
 #[derive(Debug)] pub struct Sign(Builder);
 
+/// A sign followed by a dot, which could be the start of a number like `+.5` or of an identifier
+/// like `+.foo`.
+#[derive(Debug)] pub struct SignDot(Builder);
+
 impl Sign {
     pub fn new(b: Builder) -> Sign {
         Sign(b)
     }
 
     pub fn with_char(b: &Builder, c: char) -> Option<Sign> {
-        if !b.seen_sign() {
-            match c {
-                '+' => {
-                    let mut b = b.clone();
-                    b.push_sign(Sgn::Pos);
-                    Some(Sign::new(b))
-                },
-                '-' => {
-                    let mut b = b.clone();
-                    b.push_sign(Sgn::Neg);
-                    Some(Sign::new(b))
-                },
-                _ => None
-            }
-        } else {
-            None
+        if b.seen_sign() {
+            return None;
         }
+        Sgn::from(c).map(|sign| {
+            let mut b = b.clone();
+            b.push_sign(sign);
+            Sign::new(b)
+        })
     }
 }
 
 impl State for Sign {
     fn lex(&mut self, c: char) -> StateResult {
-        StateResult::fail(Error::invalid_char(c))
+        if let Some(st) = Digit::with_char(&self.0, c) {
+            StateResult::advance(Box::new(st))
+        } else if c.is_dot() && self.0.push_decimal_point().is_ok() {
+            StateResult::advance(Box::new(SignDot(self.0.clone())))
+        } else if let Some(st) = InfNan::with_char(&self.0, c) {
+            StateResult::advance(Box::new(st))
+        } else if !could_be_identifier(&self.0) {
+            StateResult::fail(Error::invalid_char(c))
+        } else if c.is_identifier_delimiter() {
+            StateResult::emit(Token::Id(self.0.sign_char().to_string()), Resume::Here)
+        } else if c.is_sign_subsequent() {
+            StateResult::advance(Box::new(IdSub::new(format!("{}{}", self.0.sign_char(), c))))
+        } else {
+            StateResult::fail(Error::invalid_char(c))
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        if could_be_identifier(&self.0) {
+            Ok(Some(Token::Id(self.0.sign_char().to_string())))
+        } else {
+            Err(Error::unexpected_eof())
+        }
+    }
+}
+
+impl State for SignDot {
+    fn lex(&mut self, c: char) -> StateResult {
+        if self.0.push_fraction_digit(c).is_ok() {
+            StateResult::advance(Box::new(Decimal::new(self.0.clone())))
+        } else if could_be_identifier(&self.0) && c.is_dot_subsequent() {
+            StateResult::advance(Box::new(IdSub::new(format!("{}.{}", self.0.sign_char(), c))))
+        } else {
+            StateResult::fail(Error::invalid_char(c))
+        }
     }
 
     fn none(&mut self) -> Result<Option<Token>, Error> {
         Err(Error::unexpected_eof())
     }
 }
+
+/// A sign can start an identifier, as long as it isn't part of a number with a prefix or the second
+/// part of a complex number.
+pub fn could_be_identifier(b: &Builder) -> bool {
+    !b.seen_prefix() && !b.seen_first()
+}
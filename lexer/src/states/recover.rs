@@ -0,0 +1,68 @@
+/* lexer/src/states/recover.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use chars::Lexable;
+use error::Error;
+use states::{Resume, State, StateResult};
+use states::string::Quote;
+use token::Token;
+
+/// Skips the rest of a bad token after an error, up to the next delimiter.
+#[derive(Debug)] pub struct Skip;
+
+/// Skips the rest of a bad string or `|` quoted identifier after an error, through its closing
+/// quote.
+#[derive(Debug)]
+pub struct SkipQuoted {
+    quote: Quote,
+    escaped: bool,
+}
+
+impl Skip {
+    pub fn new() -> Skip {
+        Skip{}
+    }
+}
+
+impl State for Skip {
+    fn lex(&mut self, c: char) -> StateResult {
+        if c.is_identifier_delimiter() {
+            StateResult::Discard(Resume::Here)
+        } else {
+            StateResult::Continue
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Ok(None)
+    }
+
+    fn between_tokens(&self) -> bool {
+        true
+    }
+}
+
+impl SkipQuoted {
+    pub fn new(quote: Quote) -> SkipQuoted {
+        SkipQuoted { quote, escaped: false }
+    }
+}
+
+impl State for SkipQuoted {
+    fn lex(&mut self, c: char) -> StateResult {
+        if self.escaped {
+            self.escaped = false;
+        } else if c == '\\' {
+            self.escaped = true;
+        } else if self.quote.closes(c) {
+            return StateResult::Discard(Resume::AtNext);
+        }
+        StateResult::Continue
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        // The error that got us here already covers the missing quote.
+        Ok(None)
+    }
+}
@@ -0,0 +1,195 @@
+/* lexer/src/states/string.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use std::mem;
+use chars::Lexable;
+use error::{Error, ErrorKind};
+use states::{Resume, State, StateResult};
+use states::recover::SkipQuoted;
+use token::Token;
+
+/// The body of a string literal or a `|` quoted identifier, after the opening quote.
+#[derive(Debug)] pub struct Str(String, Quote);
+
+/// A backslash inside a string literal or quoted identifier.
+#[derive(Debug)] pub struct Escape(String, Quote);
+
+/// A `\x<hex>;` escape inside a string literal or quoted identifier. Holds the value so far and the
+/// hex digits so far.
+#[derive(Debug)] pub struct HexEscape(String, String, Quote);
+
+/// A `\<intraline whitespace>*<line ending><intraline whitespace>*` line continuation.
+#[derive(Debug)]
+pub struct LineContinuation {
+    value: String,
+    seen_newline: bool,
+    after_cr: bool,
+}
+
+/// The quote character that opened the token, which determines what closes it and what kind of
+/// token it becomes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Quote { Double, Pipe }
+
+impl Quote {
+    pub fn closes(&self, c: char) -> bool {
+        match *self {
+            Quote::Double => c.is_double_quote(),
+            Quote::Pipe => c.is_pipe(),
+        }
+    }
+
+    fn token(&self, value: String) -> Token {
+        match *self {
+            Quote::Double => Token::Str(value),
+            Quote::Pipe => Token::Id(value),
+        }
+    }
+
+    fn unterminated(&self) -> Error {
+        match *self {
+            Quote::Double => Error::unterminated_string(),
+            Quote::Pipe => Error::unterminated_identifier(),
+        }
+    }
+}
+
+impl Str {
+    pub fn new() -> Str {
+        Str(String::new(), Quote::Double)
+    }
+
+    /// The body of an identifier quoted with `|`, like `|hello world|`.
+    pub fn pipe() -> Str {
+        Str(String::new(), Quote::Pipe)
+    }
+
+    fn with_value(value: String, quote: Quote) -> Str {
+        Str(value, quote)
+    }
+}
+
+impl State for Str {
+    fn lex(&mut self, c: char) -> StateResult {
+        if self.1.closes(c) {
+            let value = mem::take(&mut self.0);
+            StateResult::emit(self.1.token(value), Resume::AtNext)
+        } else if c == '\\' {
+            let value = mem::take(&mut self.0);
+            StateResult::advance(Box::new(Escape(value, self.1)))
+        } else {
+            self.0.push(c);
+            StateResult::Continue
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Err(self.1.unterminated())
+    }
+}
+
+impl State for Escape {
+    fn lex(&mut self, c: char) -> StateResult {
+        let mut value = mem::take(&mut self.0);
+        let quote = self.1;
+        if quote == Quote::Pipe && c.is_whitespace() {
+            // Line continuations are only allowed in strings.
+            return StateResult::fail(Error::bad_escape(c));
+        }
+        let escaped = match c {
+            'a' => '\x07',
+            'b' => '\x08',
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            '"' => '"',
+            '\\' => '\\',
+            '|' => '|',
+            'x' | 'X' => return StateResult::advance(Box::new(HexEscape(value, String::new(), quote))),
+            ' ' | '\t' => return StateResult::advance(Box::new(LineContinuation::new(value, false, false))),
+            '\n' => return StateResult::advance(Box::new(LineContinuation::new(value, true, false))),
+            '\r' => return StateResult::advance(Box::new(LineContinuation::new(value, true, true))),
+            _ => return StateResult::fail(Error::bad_escape(c)),
+        };
+        value.push(escaped);
+        StateResult::advance(Box::new(Str::with_value(value, quote)))
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Err(self.1.unterminated())
+    }
+
+    fn recover(&self) -> Box<State> {
+        Box::new(SkipQuoted::new(self.1))
+    }
+}
+
+impl State for HexEscape {
+    fn lex(&mut self, c: char) -> StateResult {
+        if c.is_ascii_hexdigit() {
+            self.1.push(c);
+            StateResult::Continue
+        } else if c == ';' && !self.1.is_empty() {
+            let scalar = u32::from_str_radix(&self.1, 16).ok().and_then(::std::char::from_u32);
+            match scalar {
+                Some(scalar) => {
+                    let mut value = mem::take(&mut self.0);
+                    value.push(scalar);
+                    StateResult::advance(Box::new(Str::with_value(value, self.2)))
+                },
+                None => StateResult::fail(Error::new(ErrorKind::BadEscape, format!("invalid scalar value in escape: \\x{};", self.1))),
+            }
+        } else {
+            StateResult::fail(Error::bad_escape(c))
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Err(self.2.unterminated())
+    }
+
+    fn recover(&self) -> Box<State> {
+        Box::new(SkipQuoted::new(self.2))
+    }
+}
+
+impl LineContinuation {
+    fn new(value: String, seen_newline: bool, after_cr: bool) -> LineContinuation {
+        LineContinuation { value, seen_newline, after_cr }
+    }
+}
+
+impl State for LineContinuation {
+    fn lex(&mut self, c: char) -> StateResult {
+        let after_cr = self.after_cr;
+        self.after_cr = false;
+        match c {
+            ' ' | '\t' => StateResult::Continue,
+            '\n' if after_cr => StateResult::Continue,
+            '\n' | '\r' if !self.seen_newline => {
+                self.seen_newline = true;
+                self.after_cr = c == '\r';
+                StateResult::Continue
+            },
+            _ if self.seen_newline => {
+                // The continuation is over. Hand this character to a new string body state.
+                let value = mem::take(&mut self.value);
+                let mut next = Str::with_value(value, Quote::Double);
+                match next.lex(c) {
+                    StateResult::Continue => StateResult::advance(Box::new(next)),
+                    result => result,
+                }
+            },
+            _ => StateResult::fail(Error::bad_escape(c)),
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Err(Error::unterminated_string())
+    }
+
+    fn recover(&self) -> Box<State> {
+        Box::new(SkipQuoted::new(Quote::Double))
+    }
+}
@@ -0,0 +1,37 @@
+/* lexer/src/states/vector.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use chars::Lexable;
+use error::Error;
+use states::{Resume, State, StateResult};
+use token::Token;
+
+/// The rest of a `#u8(` bytevector opener, after the `#u`.
+#[derive(Debug)]
+pub struct ByteVector {
+    seen_eight: bool,
+}
+
+impl ByteVector {
+    pub fn new() -> ByteVector {
+        ByteVector { seen_eight: false }
+    }
+}
+
+impl State for ByteVector {
+    fn lex(&mut self, c: char) -> StateResult {
+        if !self.seen_eight && c == '8' {
+            self.seen_eight = true;
+            StateResult::Continue
+        } else if self.seen_eight && c.is_left_paren() {
+            StateResult::emit(Token::ByteVectorStart, Resume::AtNext)
+        } else {
+            StateResult::fail(Error::invalid_char(c))
+        }
+    }
+
+    fn none(&mut self) -> Result<Option<Token>, Error> {
+        Err(Error::unexpected_eof())
+    }
+}
@@ -4,7 +4,7 @@
 
 use error::Error;
 use states::{Resume, State, StateResult};
-use token::Token;
+use token::{Token, TriviaKind};
 
 #[derive(Debug)]
 pub struct Whitespace;
@@ -27,4 +27,12 @@ impl State for Whitespace {
     fn none(&mut self) -> Result<Option<Token>, Error> {
         Ok(None)
     }
+
+    fn between_tokens(&self) -> bool {
+        true
+    }
+
+    fn trivia(&self) -> TriviaKind {
+        TriviaKind::Whitespace
+    }
 }
@@ -2,36 +2,166 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
-#[derive(Debug, Eq, PartialEq)]
+use std::rc::Rc;
+use sibiltypes::{Frac, Int, Irr};
+use span::{Pos, Span};
+
+/// A token, the text it was lexed from, and where that text is in the input. A Lexer that keeps
+/// trivia also attaches the whitespace and comments around the token.
+#[derive(Debug, PartialEq)]
 pub struct Lex {
     token: Token,
     value: String,
-    line: usize,
-    offset: usize,
+    span: Span,
+    /// Trivia between the previous token and this one.
+    leading: Vec<Trivia>,
+    /// Trivia after this token, up to and including the end of its line.
+    trailing: Vec<Trivia>,
+}
+
+/// A piece of input that isn't part of any token.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trivia {
+    kind: TriviaKind,
+    text: String,
+    span: Span,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TriviaKind {
+    /// Spaces, tabs and other whitespace, except line endings.
+    Whitespace,
+    /// A line ending: `\n`, `\r\n` or `\r`.
+    Newline,
+    /// A `;` line comment, not including the line ending, or a `#| ... |#` block comment.
+    Comment,
+    /// The text of a token that couldn't be lexed.
+    Skipped,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum Token {
     Bool(bool),
+    Char(char),
+    /// A complex number literal with a nonzero imaginary part, as real and imaginary parts. Polar
+    /// literals like `1@2` are converted to rectangular form.
+    Complex(Num, Num),
+    /// The `#u8(` that opens a bytevector. The bytevector is closed by a `RightParen`.
+    ByteVectorStart,
+    /// A `#;` datum comment. The parser should skip the datum following this token.
+    DatumComment,
+    /// A `#n=` datum label. The datum following this token can be referred to by `DatumRef(n)`.
+    DatumLabel(usize),
+    /// A `#n#` reference to the datum labeled `#n=`.
+    DatumRef(usize),
     Dot,
-    Id,
+    /// The end of the input. Only a Lexer that keeps trivia emits this, to hold the trivia at the
+    /// end of the input.
+    Eof,
+    /// An identifier. The payload is the name of the identifier, with the `|` quotes removed and
+    /// escapes processed.
+    Id(String),
     LeftParen,
-    Num(i64),
+    Num(Num),
+    Quasiquote,
     Quote,
     RightParen,
+    /// A string literal. The payload is the value of the string with all escapes processed.
+    Str(String),
+    Unquote,
+    UnquoteSplicing,
+    /// The `#(` that opens a vector. The vector is closed by a `RightParen`.
+    VectorStart,
+}
+
+/// The value of a number literal. Exact numbers are integers or fractions; inexact numbers are
+/// floating point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Num {
+    Int(Int),
+    Frac(Frac),
+    Irr(Irr),
 }
 
 impl Lex {
-    pub fn new(token: Token, value: &str, line: usize, offset: usize) -> Lex {
+    pub fn new(token: Token, value: &str, span: Span) -> Lex {
         Lex {
             token: token,
             value: String::from(value),
-            line: line,
-            offset: offset,
+            span,
+            leading: Vec::new(),
+            trailing: Vec::new(),
         }
     }
 
-    pub fn token(&self) -> Token { self.token }
+    /// A Lex for `value` starting at `start`. The span ends just after `value`.
+    pub fn at(token: Token, value: &str, start: Pos) -> Lex {
+        Lex::new(token, value, Span::new(start, start.after_str(value)))
+    }
+
+    pub fn token(&self) -> &Token { &self.token }
     pub fn value(&self) -> &str { self.value.as_str() }
+    pub fn span(&self) -> &Span { &self.span }
+    pub fn leading(&self) -> &[Trivia] { &self.leading }
+    pub fn trailing(&self) -> &[Trivia] { &self.trailing }
+
+    /// The text of this token with all of its trivia.
+    pub fn full_text(&self) -> String {
+        let mut text = String::new();
+        for trivia in &self.leading {
+            text.push_str(trivia.text());
+        }
+        text.push_str(&self.value);
+        for trivia in &self.trailing {
+            text.push_str(trivia.text());
+        }
+        text
+    }
+
+    pub(crate) fn set_leading(&mut self, trivia: Vec<Trivia>) {
+        self.leading = trivia;
+    }
+
+    pub(crate) fn set_trailing(&mut self, trivia: Vec<Trivia>) {
+        self.trailing = trivia;
+    }
 }
 
+
+impl Trivia {
+    pub fn new(kind: TriviaKind, text: &str, span: Span) -> Trivia {
+        Trivia { kind, text: String::from(text), span }
+    }
+
+    /// Break `text`, which was discarded by the lexer as `kind`, into Trivia. Line endings in
+    /// whitespace, and the one at the end of a line comment, become their own Newline trivia.
+    pub(crate) fn split(kind: TriviaKind, text: &str, start: Pos, file: Option<Rc<str>>) -> Vec<Trivia> {
+        let mut out = Vec::new();
+        let mut start = start;
+        let mut rest = text;
+        while !rest.is_empty() {
+            let newline = match kind {
+                TriviaKind::Whitespace => rest.find(['\n', '\r']),
+                TriviaKind::Comment if rest.ends_with("\r\n") => Some(rest.len() - 2),
+                TriviaKind::Comment if rest.ends_with('\n') => Some(rest.len() - 1),
+                _ => None,
+            };
+            let (piece_kind, len) = match newline {
+                Some(0) if rest.starts_with("\r\n") => (TriviaKind::Newline, 2),
+                Some(0) => (TriviaKind::Newline, 1),
+                Some(n) => (kind, n),
+                None => (kind, rest.len()),
+            };
+            let (piece, tail) = rest.split_at(len);
+            let end = start.after_str(piece);
+            out.push(Trivia::new(piece_kind, piece, Span::new(start, end).in_file(file.clone())));
+            start = end;
+            rest = tail;
+        }
+        out
+    }
+
+    pub fn kind(&self) -> TriviaKind { self.kind }
+    pub fn text(&self) -> &str { &self.text }
+    pub fn span(&self) -> &Span { &self.span }
+}
@@ -0,0 +1,39 @@
+/* lexer/src/trace.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # Tracing
+//!
+//! A `Lexer` can report what it's doing to a `Trace` sink, which is handy for debugging the state
+//! machine and for asserting on it in tests. Tracing is off unless a sink is set with
+//! `Lexer::set_trace`. Any `FnMut(&Event)` closure can be used as a sink.
+
+use error::Error;
+use span::Pos;
+use token::Lex;
+
+/// Something the lexer did. States are identified by their type names, e.g. `"Begin"` or `"Str"`.
+#[derive(Debug, PartialEq)]
+pub enum Event<'a> {
+    /// The lexer started a token at the given position.
+    Begin(Pos),
+    /// The state `from` consumed `c` and handed off to the state `to`.
+    Transition { from: &'static str, to: &'static str, c: char },
+    /// The state threw away the input it had consumed, e.g. at the end of a comment.
+    Discard { state: &'static str },
+    /// The lexer produced a Lex.
+    Emit(&'a Lex),
+    /// The lexer produced an error. The state `recovery` will skip the rest of the bad token.
+    Error { error: &'a Error, recovery: &'static str },
+}
+
+/// A sink for lexer `Event`s.
+pub trait Trace {
+    fn trace(&mut self, event: &Event);
+}
+
+impl<F> Trace for F where F: FnMut(&Event) {
+    fn trace(&mut self, event: &Event) {
+        self(event)
+    }
+}
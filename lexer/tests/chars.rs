@@ -0,0 +1,59 @@
+/* lexer/tests/chars.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for lexing character literals.
+
+extern crate sibillexer;
+
+use sibillexer::{ErrorKind, Lex, Lexer, Pos, Token};
+
+#[test]
+fn single_chars() {
+    let mut lex = Lexer::new("#\\a #\\Z #\\λ".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('a'), "#\\a", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('Z'), "#\\Z", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('λ'), "#\\λ", Pos::new(1, 9, 8)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn delimiter_chars() {
+    let mut lex = Lexer::new("(#\\( #\\) #\\ )".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('('), "#\\(", Pos::new(1, 2, 1)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char(')'), "#\\)", Pos::new(1, 6, 5)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char(' '), "#\\ ", Pos::new(1, 10, 9)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 13, 12)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn named_chars() {
+    let names = [("alarm", '\x07'), ("backspace", '\x08'), ("delete", '\x7F'), ("escape", '\x1B'),
+                 ("newline", '\n'), ("null", '\0'), ("return", '\r'), ("space", ' '), ("tab", '\t')];
+    for &(name, c) in names.iter() {
+        let input = format!("#\\{}", name);
+        let mut lex = Lexer::new(input.chars());
+        assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char(c), &input, Pos::new(1, 1, 0)))));
+        assert_eq!(lex.next(), None);
+    }
+}
+
+#[test]
+fn hex_chars() {
+    let mut lex = Lexer::new("#\\x41 #\\x3bb #\\x".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('A'), "#\\x41", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('λ'), "#\\x3bb", Pos::new(1, 7, 6)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('x'), "#\\x", Pos::new(1, 14, 13)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn unknown_names_are_errors() {
+    let mut lex = Lexer::new("#\\bogus".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.to_string(), "1:1: unknown character name: bogus");
+    assert_eq!(lex.next(), None);
+}
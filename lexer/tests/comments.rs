@@ -0,0 +1,73 @@
+/* lexer/tests/comments.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for lexing comments.
+
+extern crate sibillexer;
+
+use sibillexer::{ErrorKind, Lex, Lexer, Pos, Token};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+#[test]
+fn line_comments_are_skipped() {
+    let mut lex = Lexer::new("; a comment\nabc ; another\n  def".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(2, 1, 12)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("def"), "def", Pos::new(3, 3, 28)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn line_comment_at_end_of_input() {
+    let mut lex = Lexer::new("abc;def".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn block_comments_are_skipped() {
+    let mut lex = Lexer::new("(#| a\nb |#abc)".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(2, 5, 10)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(2, 8, 13)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn block_comments_nest() {
+    let mut lex = Lexer::new("#| a #| b\n |# c\n|# def".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("def"), "def", Pos::new(3, 4, 19)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn block_comment_edge_cases() {
+    let mut lex = Lexer::new("#||# #|#|# |#|# abc".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 17, 16)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn unterminated_block_comment() {
+    let mut lex = Lexer::new("abc #| #| |#".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 1, 0)))));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(err.to_string(), "1:5: unterminated block comment");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn datum_comments() {
+    let mut lex = Lexer::new("#;(abc) #;def".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 3, 2)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 4, 3)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 7, 6)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 9, 8)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("def"), "def", Pos::new(1, 11, 10)))));
+    assert_eq!(lex.next(), None);
+}
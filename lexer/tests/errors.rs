@@ -0,0 +1,73 @@
+/* lexer/tests/errors.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests that the lexer reports errors with their locations and then picks up again after them.
+
+extern crate sibillexer;
+
+use sibillexer::{ErrorKind, Lex, Lexer, Pos, Token};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+#[test]
+fn lexing_continues_after_a_bad_token() {
+    let mut lex = Lexer::new("(abc #q{} def)".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 2, 1)))));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!((err.line(), err.column(), err.offset()), (1, 7, 6));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("def"), "def", Pos::new(1, 11, 10)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 14, 13)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn bad_token_ending_at_a_delimiter() {
+    let mut lex = Lexer::new("(1/x)".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::InvalidChar);
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn errors_report_byte_offsets() {
+    let mut lex = Lexer::new("λ\n  ü #z".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("λ"), "λ", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("ü"), "ü", Pos::new(2, 3, 5)))));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!((err.line(), err.column(), err.offset()), (2, 6, 9));
+    assert_eq!(err.to_string(), "2:6: invalid character: z");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn bad_escapes_skip_the_rest_of_the_string() {
+    let mut lex = Lexer::new(r#""a\qb c" d"#.chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BadEscape);
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("d"), "d", Pos::new(1, 10, 9)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn escaped_quotes_do_not_end_recovery() {
+    let mut lex = Lexer::new(r#""\q \" x" y"#.chars());
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::BadEscape);
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("y"), "y", Pos::new(1, 11, 10)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn several_errors_in_a_row() {
+    let mut lex = Lexer::new("#q 1/0 #\\bogus ok".chars());
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::InvalidChar);
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::BadNumber);
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::InvalidChar);
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("ok"), "ok", Pos::new(1, 16, 15)))));
+    assert_eq!(lex.next(), None);
+}
@@ -3,27 +3,37 @@
  */
 
 extern crate sibillexer;
+extern crate sibiltypes;
 
-use sibillexer::{Lexer, Lex, Token};
+use sibillexer::{Lex, Lexer, Num, Pos, Token};
+use sibiltypes::Int;
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+fn int(i: i64) -> Token {
+    Token::Num(Num::Int(Int(i)))
+}
 
 #[test]
 fn addition() {
     let mut lex = Lexer::new("(+ 3 4)".chars());
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::LeftParen, "(", 0, 0))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Id, "+", 0, 1))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(3), "3", 0, 3))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(4), "4", 0, 5))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::RightParen, ")", 0, 6))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+"), "+", Pos::new(1, 2, 1)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(3), "3", Pos::new(1, 4, 3)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(4), "4", Pos::new(1, 6, 5)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 7, 6)))));
     assert_eq!(lex.next(), None);
 }
 
 #[test]
 fn subtraction() {
     let mut lex = Lexer::new("(- 3 4)".chars());
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::LeftParen, "(", 0, 0))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Id, "-", 0, 1))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(3), "3", 0, 3))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(4), "4", 0, 5))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::RightParen, ")", 0, 6))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("-"), "-", Pos::new(1, 2, 1)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(3), "3", Pos::new(1, 4, 3)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(4), "4", Pos::new(1, 6, 5)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 7, 6)))));
     assert_eq!(lex.next(), None);
 }
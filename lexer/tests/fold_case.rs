@@ -0,0 +1,115 @@
+/* lexer/tests/fold_case.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for the `#!fold-case` and `#!no-fold-case` directives, and for case folding.
+
+extern crate sibillexer;
+
+use sibillexer::{ErrorKind, Lex, Lexer, Pos, Token, TriviaKind};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+#[test]
+fn identifiers_keep_their_case_by_default() {
+    let mut lex = Lexer::new("Hello".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("Hello"), "Hello", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn directives_turn_folding_on_and_off() {
+    let mut lex = Lexer::new("ABC #!fold-case ABC #!no-fold-case ABC".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("ABC"), "ABC", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "ABC", Pos::new(1, 17, 16)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("ABC"), "ABC", Pos::new(1, 36, 35)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn directive_before_a_paren() {
+    let mut lex = Lexer::new("#!fold-case(X)".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 12, 11)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("x"), "X", Pos::new(1, 13, 12)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 14, 13)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn folding_option() {
+    let mut lex = Lexer::new("Straße ΣΑΣ |Pipes Stay| #!no-fold-case Straße".chars());
+    lex.set_fold_case(true);
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("strasse"), "Straße", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("σασ"), "ΣΑΣ", Pos::new(1, 8, 8)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("Pipes Stay"), "|Pipes Stay|", Pos::new(1, 12, 15)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("Straße"), "Straße", Pos::new(1, 40, 43)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn peculiar_identifiers_fold() {
+    let mut lex = Lexer::new("#!fold-case +Inf ->X".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+inf"), "+Inf", Pos::new(1, 13, 12)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("->x"), "->X", Pos::new(1, 18, 17)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn character_names_fold_but_characters_dont() {
+    let mut lex = Lexer::new("#!fold-case #\\SPACE #\\NewLine #\\X41 #\\A".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char(' '), "#\\SPACE", Pos::new(1, 13, 12)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('\n'), "#\\NewLine", Pos::new(1, 21, 20)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('A'), "#\\X41", Pos::new(1, 31, 30)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Char('A'), "#\\A", Pos::new(1, 37, 36)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn character_names_are_case_sensitive_without_folding() {
+    let mut lex = Lexer::new("#\\SPACE".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.to_string(), "1:1: unknown character name: SPACE");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn unknown_directives() {
+    let mut lex = Lexer::new("#!FOLD-CASE abc #! def".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnknownDirective);
+    assert_eq!(err.to_string(), "1:12: unknown directive: #!FOLD-CASE");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 13, 12)))));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.to_string(), "1:19: unknown directive: #!");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("def"), "def", Pos::new(1, 20, 19)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn directive_at_end_of_input() {
+    let mut lex = Lexer::new("abc #!fold-case".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn directives_are_comment_trivia() {
+    let mut lex = Lexer::new("#!fold-case\nABC".chars());
+    lex.set_keep_trivia(true);
+    let abc = lex.next().unwrap().unwrap();
+    assert_eq!(abc.token(), &id("abc"));
+    let leading: Vec<_> = abc.leading().iter().map(|t| (t.kind(), t.text())).collect();
+    assert_eq!(leading, vec![(TriviaKind::Comment, "#!fold-case"), (TriviaKind::Newline, "\n")]);
+}
+
+#[test]
+fn resumable_lexers_wait_for_the_end_of_a_directive() {
+    let mut lex = Lexer::resumable();
+    lex.feed("#!fold");
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::NeedMoreInput);
+    lex.feed("-case X\n");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("x"), "X", Pos::new(1, 13, 12)))));
+}
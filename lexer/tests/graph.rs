@@ -0,0 +1,34 @@
+/* lexer/tests/graph.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Checks that the documented state graph matches the lexer.
+
+extern crate sibillexer;
+
+use sibillexer::graph;
+
+#[test]
+fn documented_graph_matches_the_lexer() {
+    let documented = include_str!("../../doc/lexer.dot");
+    let actual = graph::dot();
+    if documented != actual {
+        let diff: Vec<String> = {
+            let doc_lines: Vec<&str> = documented.lines().collect();
+            let actual_lines: Vec<&str> = actual.lines().collect();
+            let missing = actual_lines.iter().filter(|l| !doc_lines.contains(l)).map(|l| format!("+ {}", l));
+            let extra = doc_lines.iter().filter(|l| !actual_lines.contains(l)).map(|l| format!("- {}", l));
+            missing.chain(extra).collect()
+        };
+        panic!("doc/lexer.dot is out of date. Update it with `cargo run --example dot > ../doc/lexer.dot`.\n{}",
+               diff.join("\n"));
+    }
+}
+
+#[test]
+fn graph_starts_at_begin() {
+    let dot = graph::dot();
+    assert!(dot.starts_with("digraph lexer {\n"));
+    assert!(dot.contains("    Begin -> \"Token::LeftParen\" [ label = \"(\" ];\n"));
+    assert!(dot.contains("    Hash -> Bool [ label = \"F,T,f,t\" ];\n"));
+}
@@ -0,0 +1,109 @@
+/* lexer/tests/identifiers.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for lexing identifiers, including `|` quoted and peculiar identifiers.
+
+extern crate sibillexer;
+
+use sibillexer::{ErrorKind, Lex, Lexer, Pos, Token};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+#[test]
+fn pipe_identifiers() {
+    let mut lex = Lexer::new("|hello world| ||".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("hello world"), "|hello world|", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id(""), "||", Pos::new(1, 15, 14)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn pipe_identifier_escapes() {
+    let mut lex = Lexer::new(r"|a\|b\\c\x41;\t|".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("a|b\\cA\t"), r"|a\|b\\c\x41;\t|", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn pipes_are_delimiters() {
+    let mut lex = Lexer::new("abc|def|(|x|)".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("def"), "|def|", Pos::new(1, 4, 3)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 9, 8)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("x"), "|x|", Pos::new(1, 10, 9)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 13, 12)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn unterminated_pipe_identifier() {
+    let mut lex = Lexer::new("|abc".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnterminatedString);
+    assert_eq!(err.to_string(), "1:1: unterminated identifier");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn pipe_identifiers_have_no_line_continuations() {
+    let mut lex = Lexer::new("|ab\\ \ncd|".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BadEscape);
+    assert_eq!(err.to_string(), "1:5: invalid escape sequence: \\ ");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn unicode_identifiers() {
+    let mut lex = Lexer::new("λ straße x² ☃".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("λ"), "λ", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("straße"), "straße", Pos::new(1, 3, 3)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("x²"), "x²", Pos::new(1, 10, 11)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("☃"), "☃", Pos::new(1, 13, 15)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn peculiar_identifiers() {
+    let mut lex = Lexer::new("+ - ... +.foo -.. -@ +-".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+"), "+", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("-"), "-", Pos::new(1, 3, 2)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("..."), "...", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+.foo"), "+.foo", Pos::new(1, 9, 8)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("-.."), "-..", Pos::new(1, 15, 14)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("-@"), "-@", Pos::new(1, 19, 18)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+-"), "+-", Pos::new(1, 22, 21)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn sign_dot_digit_is_always_a_number() {
+    let mut lex = Lexer::new("+.a1 +.1a".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+.a1"), "+.a1", Pos::new(1, 1, 0)))));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.to_string(), "1:9: invalid character: a");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn unicode_identifiers_by_general_category() {
+    let mut lex = Lexer::new("²x \u{301}x x\u{903} €".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("²x"), "²x", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("\u{301}x"), "\u{301}x", Pos::new(1, 4, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("x\u{903}"), "x\u{903}", Pos::new(1, 7, 8)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("€"), "€", Pos::new(1, 10, 13)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn quotation_marks_and_leading_spacing_marks_are_not_identifiers() {
+    for input in &["«a", "a«", "\u{903}x"] {
+        let mut lex = Lexer::new(input.chars());
+        let err = lex.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidChar, "{}", input);
+    }
+}
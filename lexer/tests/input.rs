@@ -0,0 +1,108 @@
+/* lexer/tests/input.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for lexing from readers and from input that arrives a piece at a time.
+
+extern crate sibillexer;
+
+use std::io::{self, BufReader, Read};
+use sibillexer::{ErrorKind, Lex, Lexer, Pos, Token};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+#[test]
+fn reads_utf8_from_a_reader() {
+    let mut lex = Lexer::from_reader("(λ \"ünï\")".as_bytes());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("λ"), "λ", Pos::new(1, 2, 1)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Str("ünï".to_string()), "\"ünï\"", Pos::new(1, 4, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 9, 11)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn reads_across_buffer_boundaries() {
+    // A one byte buffer splits every multibyte character.
+    let reader = BufReader::with_capacity(1, "☃ x".as_bytes());
+    let mut lex = Lexer::from_reader(reader);
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("☃"), "☃", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("x"), "x", Pos::new(1, 3, 4)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn invalid_utf8_is_an_error() {
+    let input: &[u8] = b"ab\xff cd \xe2\x98 ef \xed\xa0\x80";
+    let mut lex = Lexer::from_reader(input);
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidUtf8);
+    assert_eq!(err.to_string(), "1:3: invalid UTF-8: \\xff");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("cd"), "cd", Pos::new(1, 5, 4)))));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.to_string(), "1:8: invalid UTF-8: \\xe2\\x98");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("ef"), "ef", Pos::new(1, 10, 10)))));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!((err.to_string().as_str(), err.offset()), ("1:13: invalid UTF-8: \\xed\\xa0\\x80", 13));
+    assert_eq!(lex.next(), None);
+}
+
+struct Broken;
+
+impl Read for Broken {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "broken"))
+    }
+}
+
+#[test]
+fn read_errors_end_the_input() {
+    let mut lex = Lexer::from_reader(BufReader::new(Broken));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Io);
+    assert_eq!(err.to_string(), "1:1: couldn't read input: broken");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn resumable_lexer_waits_for_more_input() {
+    let mut lex = Lexer::resumable();
+    lex.feed("(ab \"cd");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("ab"), "ab", Pos::new(1, 2, 1)))));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!((err.kind(), err.pos()), (ErrorKind::NeedMoreInput, Pos::new(1, 5, 4)));
+
+    lex.feed("\nef\" gh");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Str("cd\nef".to_string()), "\"cd\nef\"", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::NeedMoreInput);
+
+    lex.feed(")\n");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("gh"), "gh", Pos::new(2, 5, 12)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(2, 7, 14)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn resumable_lexer_waits_quietly_between_tokens() {
+    let mut lex = Lexer::resumable();
+    lex.feed("ab ; comment");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+    lex.feed(" still\ncd");
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::NeedMoreInput);
+    lex.finish();
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("cd"), "cd", Pos::new(2, 1, 19)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn finished_input_fails_unfinished_tokens() {
+    let mut lex = Lexer::resumable();
+    lex.feed("\"abc");
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::NeedMoreInput);
+    lex.finish();
+    assert_eq!(lex.next().unwrap().unwrap_err().kind(), ErrorKind::UnterminatedString);
+}
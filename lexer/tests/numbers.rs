@@ -5,31 +5,242 @@
 //! Tests for lexing numbers.
 
 extern crate sibillexer;
+extern crate sibiltypes;
 
-use sibillexer::{Lexer, Lex, Token};
+use sibillexer::{ErrorKind, Lex, Lexer, Num, Pos, Token};
+use sibiltypes::{Frac, Int, Irr};
+
+fn int(i: i64) -> Token {
+    Token::Num(Num::Int(Int(i)))
+}
+
+fn frac(p: i64, q: i64) -> Token {
+    Token::Num(Num::Frac(Frac::from_ints(p, q).unwrap()))
+}
+
+fn irr(f: f64) -> Token {
+    Token::Num(Num::Irr(Irr(f)))
+}
 
 #[test]
 fn ints_simple() {
     let mut lex = Lexer::new("23 42 0".chars());
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(23), "23", 0, 0))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(42), "42", 0, 3))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(0), "0", 0, 6))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(23), "23", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(42), "42", Pos::new(1, 4, 3)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(0), "0", Pos::new(1, 7, 6)))));
     assert_eq!(lex.next(), None);
 }
 
 #[test]
 fn ints_negative() {
     let mut lex = Lexer::new("-56".chars());
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(-56), "-56", 0, 0))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(-56), "-56", Pos::new(1, 1, 0)))));
     assert_eq!(lex.next(), None);
 }
 
 #[test]
 fn ints_alternative_bases() {
     let mut lex = Lexer::new("#x2A #b11001 #o56 #d78".chars());
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(0x2A), "#x2A", 0, 0))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(0b11001), "#b11001", 0, 5))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(0o56), "#o56", 0, 13))));
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Num(78), "#d78", 0, 18))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(0x2A), "#x2A", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(0b11001), "#b11001", Pos::new(1, 6, 5)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(0o56), "#o56", Pos::new(1, 14, 13)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(78), "#d78", Pos::new(1, 19, 18)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn ints_with_explicit_signs() {
+    let mut lex = Lexer::new("+12 #x-1F #b+101".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(12), "+12", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(-0x1F), "#x-1F", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(5), "#b+101", Pos::new(1, 11, 10)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn rationals() {
+    let mut lex = Lexer::new("1/2 -6/4 #x-1F/3 #b101/10 4/2".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(frac(1, 2), "1/2", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(frac(-3, 2), "-6/4", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(frac(-31, 3), "#x-1F/3", Pos::new(1, 10, 9)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(frac(5, 2), "#b101/10", Pos::new(1, 18, 17)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(2), "4/2", Pos::new(1, 27, 26)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn decimals() {
+    let mut lex = Lexer::new("2.75 .5 -0.25 12. #d1.5".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(2.75), "2.75", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(0.5), ".5", Pos::new(1, 6, 5)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(-0.25), "-0.25", Pos::new(1, 9, 8)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(12.0), "12.", Pos::new(1, 15, 14)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(1.5), "#d1.5", Pos::new(1, 19, 18)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn exponents() {
+    let mut lex = Lexer::new("6.02e23 1e3 -2.5E-2 .5e+1".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(6.02e23), "6.02e23", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(1000.0), "1e3", Pos::new(1, 9, 8)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(-0.025), "-2.5E-2", Pos::new(1, 13, 12)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(5.0), ".5e+1", Pos::new(1, 21, 20)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn hex_digits_are_not_exponents() {
+    let mut lex = Lexer::new("#x1e3".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(0x1e3), "#x1e3", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn exactness_prefixes() {
+    let mut lex = Lexer::new("#e1.5 #e1e3 #e-1.25e-1 #i1/4 #i#x10 #x#e10 #i3".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(frac(3, 2), "#e1.5", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(1000), "#e1e3", Pos::new(1, 7, 6)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(frac(-1, 8), "#e-1.25e-1", Pos::new(1, 13, 12)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(0.25), "#i1/4", Pos::new(1, 24, 23)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(16.0), "#i#x10", Pos::new(1, 30, 29)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(16), "#x#e10", Pos::new(1, 37, 36)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(3.0), "#i3", Pos::new(1, 44, 43)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn prefixes_are_case_insensitive() {
+    let mut lex = Lexer::new("#X1F #B101 #E1.5 #I1/4 #O17 #D9 #x#E10 #E#X10".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(0x1F), "#X1F", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(5), "#B101", Pos::new(1, 6, 5)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(frac(3, 2), "#E1.5", Pos::new(1, 12, 11)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(0.25), "#I1/4", Pos::new(1, 18, 17)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(0o17), "#O17", Pos::new(1, 24, 23)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(9), "#D9", Pos::new(1, 29, 28)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(16), "#x#E10", Pos::new(1, 33, 32)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(16), "#E#X10", Pos::new(1, 40, 39)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn numbers_in_lists() {
+    let mut lex = Lexer::new("(1/2 .5)".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(frac(1, 2), "1/2", Pos::new(1, 2, 1)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(0.5), ".5", Pos::new(1, 6, 5)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 8, 7)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn decimals_must_be_base_ten() {
+    let mut lex = Lexer::new("#x1.5".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.to_string(), "1:4: invalid character: .");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn exact_division_by_zero() {
+    let mut lex = Lexer::new("1/0".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BadNumber);
+    assert_eq!(err.to_string(), "1:1: bad number: division by zero");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn ratios_of_the_smallest_int() {
+    let mut lex = Lexer::new("-9223372036854775808/5 -9223372036854775808/2".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(frac(i64::MIN, 5), "-9223372036854775808/5", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(i64::MIN / 2), "-9223372036854775808/2", Pos::new(1, 24, 23)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn exact_integers_must_fit() {
+    let mut lex = Lexer::new("123456789012345678901234567890".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BadNumber);
+    assert_eq!(err.to_string(), "1:1: bad number: too large");
+    assert_eq!(lex.next(), None);
+}
+
+fn complex(re: Num, im: Num) -> Token {
+    Token::Complex(re, im)
+}
+
+#[test]
+fn infinities() {
+    let mut lex = Lexer::new("+inf.0 -INF.0".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(f64::INFINITY), "+inf.0", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(irr(f64::NEG_INFINITY), "-INF.0", Pos::new(1, 8, 7)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn nans() {
+    let mut lex = Lexer::new("+nan.0 -nan.0".chars());
+    for _ in 0..2 {
+        match lex.next() {
+            Some(Ok(ref lex)) => match *lex.token() {
+                Token::Num(Num::Irr(Irr(f))) => assert!(f.is_nan()),
+                ref token => panic!("expected NaN, got {:?}", token),
+            },
+            other => panic!("expected NaN, got {:?}", other),
+        }
+    }
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn complex_rectangular() {
+    let mut lex = Lexer::new("1+2i 3/4-5.5i +2i -i 1+i 1-inf.0i".chars());
+    let (zero, one, two) = (Num::Int(Int(0)), Num::Int(Int(1)), Num::Int(Int(2)));
+    let three_fourths = Num::Frac(Frac::from_ints(3, 4).unwrap());
+    let neg_inf = Num::Irr(Irr(f64::NEG_INFINITY));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(complex(one, two), "1+2i", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(complex(three_fourths, Num::Irr(Irr(-5.5))),
+                                              "3/4-5.5i", Pos::new(1, 6, 5)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(complex(zero, two), "+2i", Pos::new(1, 15, 14)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(complex(zero, Num::Int(Int(-1))), "-i", Pos::new(1, 19, 18)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(complex(one, one), "1+i", Pos::new(1, 22, 21)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(complex(one, neg_inf), "1-inf.0i", Pos::new(1, 26, 25)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn complex_with_exact_zero_imaginary_part_is_real() {
+    let mut lex = Lexer::new("5+0i".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(5), "5+0i", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn complex_polar() {
+    let mut lex = Lexer::new("2@0 1@-3.5".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(int(2), "2@0", Pos::new(1, 1, 0)))));
+    let expected = complex(Num::Irr(Irr((-3.5f64).cos())), Num::Irr(Irr((-3.5f64).sin())));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(expected, "1@-3.5", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn complex_requires_i() {
+    let mut lex = Lexer::new("1+2".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BadNumber);
+    assert_eq!(err.to_string(), "1:1: bad number: imaginary part is missing an i");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn imaginary_part_needs_a_sign() {
+    let mut lex = Lexer::new("2i".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.to_string(), "1:1: invalid character: i");
     assert_eq!(lex.next(), None);
 }
@@ -6,11 +6,15 @@
 
 extern crate sibillexer;
 
-use sibillexer::{Lexer, Lex, Token};
+use sibillexer::{ErrorKind, Lex, Lexer, Pos, Token};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
 
 #[test]
 fn lexer_finds_left_paren() {
-    let expected_lex = Lex::new(Token::LeftParen, "(", 0, 0);
+    let expected_lex = Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0));
     let mut lex = Lexer::new("(".chars());
     assert_eq!(lex.next(), Some(Ok(expected_lex)));
     assert_eq!(lex.next(), None);
@@ -18,7 +22,7 @@ fn lexer_finds_left_paren() {
 
 #[test]
 fn lexer_finds_right_paren() {
-    let expected_lex = Lex::new(Token::RightParen, ")", 0, 0);
+    let expected_lex = Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0));
     let mut lex = Lexer::new(")".chars());
     assert_eq!(lex.next(), Some(Ok(expected_lex)));
     assert_eq!(lex.next(), None);
@@ -26,7 +30,7 @@ fn lexer_finds_right_paren() {
 
 #[test]
 fn lexer_finds_id() {
-    let expected_lex = Lex::new(Token::Id, "abc", 0, 0);
+    let expected_lex = Lex::at(id("abc"), "abc", Pos::new(1, 1, 0));
     let mut lex = Lexer::new("abc".chars());
     assert_eq!(lex.next(), Some(Ok(expected_lex)));
     assert_eq!(lex.next(), None);
@@ -34,7 +38,7 @@ fn lexer_finds_id() {
 
 #[test]
 fn bool_short_true() {
-    let expected_lex = Lex::new(Token::Bool(true), "#t", 0, 0);
+    let expected_lex = Lex::at(Token::Bool(true), "#t", Pos::new(1, 1, 0));
     let mut lex = Lexer::new("#t".chars());
     assert_eq!(lex.next(), Some(Ok(expected_lex)));
     assert_eq!(lex.next(), None);
@@ -42,7 +46,7 @@ fn bool_short_true() {
 
 #[test]
 fn bool_short_false() {
-    let expected_lex = Lex::new(Token::Bool(false), "#f", 0, 0);
+    let expected_lex = Lex::at(Token::Bool(false), "#f", Pos::new(1, 1, 0));
     let mut lex = Lexer::new("#f".chars());
     assert_eq!(lex.next(), Some(Ok(expected_lex)));
     assert_eq!(lex.next(), None);
@@ -50,7 +54,7 @@ fn bool_short_false() {
 
 #[test]
 fn bool_long_true() {
-    let expected_lex = Lex::new(Token::Bool(true), "#true", 0, 0);
+    let expected_lex = Lex::at(Token::Bool(true), "#true", Pos::new(1, 1, 0));
     let mut lex = Lexer::new("#true".chars());
     assert_eq!(lex.next(), Some(Ok(expected_lex)));
     assert_eq!(lex.next(), None);
@@ -58,7 +62,7 @@ fn bool_long_true() {
 
 #[test]
 fn bool_long_false() {
-    let expected_lex = Lex::new(Token::Bool(false), "#false", 0, 0);
+    let expected_lex = Lex::at(Token::Bool(false), "#false", Pos::new(1, 1, 0));
     let mut lex = Lexer::new("#false".chars());
     assert_eq!(lex.next(), Some(Ok(expected_lex)));
     assert_eq!(lex.next(), None);
@@ -67,7 +71,7 @@ fn bool_long_false() {
 #[test]
 fn bool_with_spaces() {
     // See issue #12
-    let expected_lex = Lex::new(Token::Bool(false), "#f", 0, 2);
+    let expected_lex = Lex::at(Token::Bool(false), "#f", Pos::new(1, 3, 2));
     let mut lex = Lexer::new("  #f  ".chars());
     assert_eq!(lex.next(), Some(Ok(expected_lex)));
     assert_eq!(lex.next(), None);
@@ -76,13 +80,105 @@ fn bool_with_spaces() {
 #[test]
 fn dot() {
     let mut lex = Lexer::new(".".chars());
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Dot, ".", 0, 0))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Dot, ".", Pos::new(1, 1, 0)))));
     assert_eq!(lex.next(), None);
 }
 
 #[test]
 fn quote() {
     let mut lex = Lexer::new("'".chars());
-    assert_eq!(lex.next(), Some(Ok(Lex::new(Token::Quote, "'", 0, 0))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Quote, "'", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn quasiquote_and_unquotes() {
+    let mut lex = Lexer::new("`(,a ,@b),".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Quasiquote, "`", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 2, 1)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Unquote, ",", Pos::new(1, 3, 2)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("a"), "a", Pos::new(1, 4, 3)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::UnquoteSplicing, ",@", Pos::new(1, 6, 5)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("b"), "b", Pos::new(1, 8, 7)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 9, 8)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Unquote, ",", Pos::new(1, 10, 9)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn ids_starting_with_signs() {
+    let mut lex = Lexer::new("->x + -".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("->x"), "->x", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+"), "+", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("-"), "-", Pos::new(1, 7, 6)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn ids_starting_with_dots() {
+    let mut lex = Lexer::new("... .a".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("..."), "...", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id(".a"), ".a", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn ids_that_look_like_special_numbers() {
+    let mut lex = Lexer::new("+in -nope +i2 +inf.0x".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+in"), "+in", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("-nope"), "-nope", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+i2"), "+i2", Pos::new(1, 11, 10)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("+inf.0x"), "+inf.0x", Pos::new(1, 15, 14)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn vector_starts() {
+    let mut lex = Lexer::new("#(#t) #u8(1)".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::VectorStart, "#(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Bool(true), "#t", Pos::new(1, 3, 2)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::ByteVectorStart, "#u8(", Pos::new(1, 7, 6)))));
+    assert_eq!(lex.next().map(|lex| lex.map(|lex| lex.value().to_string())),
+               Some(Ok("1".to_string())));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 12, 11)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn bytevector_start_must_be_u8() {
+    let mut lex = Lexer::new("#u9(".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.to_string(), "1:3: invalid character: 9");
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 4, 3)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn datum_labels() {
+    let mut lex = Lexer::new("#0=(a . #0#) #12=#12#".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::DatumLabel(0), "#0=", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 4, 3)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("a"), "a", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::Dot, ".", Pos::new(1, 7, 6)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::DatumRef(0), "#0#", Pos::new(1, 9, 8)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 12, 11)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::DatumLabel(12), "#12=", Pos::new(1, 14, 13)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::DatumRef(12), "#12#", Pos::new(1, 18, 17)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn bad_datum_labels() {
+    let mut lex = Lexer::new("#1x #99999999999999999999= #2".chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.to_string(), "1:3: invalid character: x");
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BadLabel);
+    assert_eq!(err.to_string(), "1:26: datum label out of range: 99999999999999999999");
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
     assert_eq!(lex.next(), None);
 }
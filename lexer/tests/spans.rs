@@ -0,0 +1,30 @@
+/* lexer/tests/spans.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for the source spans of Lexes and errors.
+
+extern crate sibillexer;
+
+use sibillexer::{Lexer, Pos, Span};
+
+#[test]
+fn spans_cover_the_whole_token() {
+    let mut lex = Lexer::new("(\"a\nbc\" λ)".chars());
+    let spans: Vec<Span> = lex.by_ref().map(|l| l.unwrap().span().clone()).collect();
+    assert_eq!(spans, vec![Span::new(Pos::new(1, 1, 0), Pos::new(1, 2, 1)),
+                           Span::new(Pos::new(1, 2, 1), Pos::new(2, 4, 7)),
+                           Span::new(Pos::new(2, 5, 8), Pos::new(2, 6, 10)),
+                           Span::new(Pos::new(2, 6, 10), Pos::new(2, 7, 11))]);
+}
+
+#[test]
+fn spans_and_errors_name_the_file() {
+    let mut lex = Lexer::with_file("abc #q".chars(), "main.scm");
+    let abc = lex.next().unwrap().unwrap();
+    assert_eq!(abc.span().file(), Some("main.scm"));
+    assert_eq!(abc.span().to_string(), "main.scm:1:1");
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.file(), Some("main.scm"));
+    assert_eq!(err.to_string(), "main.scm:1:6: invalid character: q");
+}
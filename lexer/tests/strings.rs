@@ -0,0 +1,101 @@
+/* lexer/tests/strings.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for lexing string literals.
+
+extern crate sibillexer;
+
+use sibillexer::{ErrorKind, Lex, Lexer, Pos, Token};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+fn string(value: &str) -> Token {
+    Token::Str(value.to_string())
+}
+
+#[test]
+fn empty_string() {
+    let mut lex = Lexer::new("\"\"".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(string(""), "\"\"", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn simple_string() {
+    let mut lex = Lexer::new("\"abc def\"".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(string("abc def"), "\"abc def\"", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn strings_are_delimiters() {
+    let mut lex = Lexer::new("(abc\"def\")".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 2, 1)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(string("def"), "\"def\"", Pos::new(1, 5, 4)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 10, 9)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn character_escapes() {
+    let input = r#""\a\b\t\n\r\"\\\|""#;
+    let mut lex = Lexer::new(input.chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(string("\x07\x08\t\n\r\"\\|"), input, Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn hex_escapes() {
+    let input = r#""\x41;b\x3bb;""#;
+    let mut lex = Lexer::new(input.chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(string("Abλ"), input, Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn line_continuation() {
+    let input = "\"abc \\  \n    def\"";
+    let mut lex = Lexer::new(input.chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(string("abc def"), input, Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn line_continuation_with_crlf() {
+    let input = "\"abc\\\r\n  def\"";
+    let mut lex = Lexer::new(input.chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(string("abcdef"), input, Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn literal_newlines_are_kept() {
+    let input = "\"abc\ndef\" x";
+    let mut lex = Lexer::new(input.chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(string("abc\ndef"), "\"abc\ndef\"", Pos::new(1, 1, 0)))));
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("x"), "x", Pos::new(2, 6, 10)))));
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn unterminated_string_reports_opening_quote() {
+    let mut lex = Lexer::new("abc\n  \"def\n ghi".chars());
+    assert_eq!(lex.next(), Some(Ok(Lex::at(id("abc"), "abc", Pos::new(1, 1, 0)))));
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnterminatedString);
+    assert_eq!(err.to_string(), "2:3: unterminated string");
+    assert_eq!(lex.next(), None);
+}
+
+#[test]
+fn bad_escape() {
+    let mut lex = Lexer::new(r#""a\qb""#.chars());
+    let err = lex.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BadEscape);
+    assert_eq!(err.to_string(), "1:4: invalid escape sequence: \\q");
+    assert_eq!(lex.next(), None);
+}
@@ -0,0 +1,69 @@
+/* lexer/tests/trace.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for the lexer's trace hook.
+
+extern crate sibillexer;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use sibillexer::Lexer;
+use sibillexer::trace::Event;
+
+/// Lex all of `input`, and collect the trace events as strings.
+fn trace(input: &str) -> Vec<String> {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let sink = events.clone();
+    let mut lexer = Lexer::new(input.chars());
+    lexer.set_trace(move |event: &Event| {
+        let desc = match *event {
+            Event::Begin(pos) => format!("begin {}", pos),
+            Event::Transition { from, to, c } => format!("{} -{}-> {}", from, c, to),
+            Event::Discard { state } => format!("discard {}", state),
+            Event::Emit(lex) => format!("emit {}", lex.value()),
+            Event::Error { error, recovery } => format!("error {} -> {}", error, recovery),
+        };
+        sink.borrow_mut().push(desc);
+    });
+    for _ in lexer { }
+    let out = events.borrow().clone();
+    out
+}
+
+#[test]
+fn traces_transitions_and_emits() {
+    assert_eq!(trace("#t ab"), vec!["begin 1:1",
+                                    "Begin -#-> Hash",
+                                    "Hash -t-> Bool",
+                                    "emit #t",
+                                    "begin 1:3",
+                                    "Begin - -> Whitespace",
+                                    "discard Whitespace",
+                                    "begin 1:4",
+                                    "Begin -a-> IdSub",
+                                    "emit ab",
+                                    "begin 1:6"]);
+}
+
+#[test]
+fn traces_errors_and_recovery() {
+    assert_eq!(trace("#q a"), vec!["begin 1:1",
+                                   "Begin -#-> Hash",
+                                   "error 1:2: invalid character: q -> Skip",
+                                   "begin 1:2",
+                                   "discard Skip",
+                                   "begin 1:3",
+                                   "Begin - -> Whitespace",
+                                   "discard Whitespace",
+                                   "begin 1:4",
+                                   "Begin -a-> IdSub",
+                                   "emit a",
+                                   "begin 1:5"]);
+}
+
+#[test]
+fn tracing_is_off_by_default() {
+    let lexer = Lexer::new("(a b)".chars());
+    assert_eq!(lexer.count(), 4);
+}
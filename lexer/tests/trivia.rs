@@ -0,0 +1,93 @@
+/* lexer/tests/trivia.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for keeping whitespace and comments as trivia.
+
+extern crate sibillexer;
+
+use sibillexer::{Lex, Lexer, Token, TriviaKind};
+
+fn lex_all(input: &str) -> Vec<Lex> {
+    let mut lexer = Lexer::new(input.chars());
+    lexer.set_keep_trivia(true);
+    lexer.filter_map(|l| l.ok()).collect()
+}
+
+/// The kinds and text of some trivia.
+fn trivia(lex: &Lex, leading: bool) -> Vec<(TriviaKind, &str)> {
+    let trivia = if leading { lex.leading() } else { lex.trailing() };
+    trivia.iter().map(|t| (t.kind(), t.text())).collect()
+}
+
+fn round_trip(input: &str) {
+    let mut lexer = Lexer::new(input.chars());
+    lexer.set_keep_trivia(true);
+    let text: String = lexer.filter_map(|l| l.ok()).map(|l| l.full_text()).collect();
+    assert_eq!(text, input);
+}
+
+#[test]
+fn trivia_round_trips() {
+    round_trip("");
+    round_trip("   ");
+    round_trip("(define (f x)  ; the function\n  #| block\n comment |# (+ x 1))\n\n");
+    round_trip("\r\n'a\t`(b ,c ,@d) #;(e) \"str\" #\\space #(1 2) #u8(3) |pipe id| 1/2 +i\r\n; done");
+    round_trip("#|unterminated\n");
+}
+
+#[test]
+fn trivia_attaches_to_neighboring_tokens() {
+    let lexes = lex_all("  a ; one\n\n  b c\n");
+    assert_eq!(lexes.len(), 4);
+    assert_eq!(trivia(&lexes[0], true), vec![(TriviaKind::Whitespace, "  ")]);
+    assert_eq!(trivia(&lexes[0], false), vec![(TriviaKind::Whitespace, " "),
+                                              (TriviaKind::Comment, "; one"),
+                                              (TriviaKind::Newline, "\n")]);
+    assert_eq!(trivia(&lexes[1], true), vec![(TriviaKind::Newline, "\n"),
+                                             (TriviaKind::Whitespace, "  ")]);
+    assert_eq!(trivia(&lexes[1], false), vec![(TriviaKind::Whitespace, " ")]);
+    assert_eq!(trivia(&lexes[2], true), vec![]);
+    assert_eq!(trivia(&lexes[2], false), vec![(TriviaKind::Newline, "\n")]);
+    assert_eq!(lexes[3].token(), &Token::Eof);
+    assert_eq!(trivia(&lexes[3], true), vec![]);
+}
+
+#[test]
+fn trailing_trivia_at_the_end_goes_to_eof() {
+    let lexes = lex_all("a\n  #| c |#\n");
+    assert_eq!(trivia(&lexes[0], false), vec![(TriviaKind::Newline, "\n")]);
+    assert_eq!(trivia(&lexes[1], true), vec![(TriviaKind::Whitespace, "  "),
+                                             (TriviaKind::Comment, "#| c |#"),
+                                             (TriviaKind::Newline, "\n")]);
+}
+
+#[test]
+fn trivia_has_spans() {
+    let lexes = lex_all("a\r\n  b");
+    let newline = &lexes[0].trailing()[0];
+    assert_eq!((newline.text(), newline.span().start().offset(), newline.span().end().offset()), ("\r\n", 1, 3));
+    let space = &lexes[1].leading()[0];
+    assert_eq!((space.span().start().line(), space.span().start().column()), (2, 1));
+}
+
+#[test]
+fn bad_tokens_are_skipped_trivia() {
+    let input = "a #q9 b";
+    let mut lexer = Lexer::new(input.chars());
+    lexer.set_keep_trivia(true);
+    let out: Vec<_> = lexer.collect();
+    assert_eq!(out.len(), 4);
+    assert!(out[1].is_err());
+    let b = out[2].as_ref().unwrap();
+    assert_eq!(trivia(b, true), vec![(TriviaKind::Skipped, "#q9"), (TriviaKind::Whitespace, " ")]);
+    let text: String = out.iter().filter_map(|l| l.as_ref().ok()).map(|l| l.full_text()).collect();
+    assert_eq!(text, input);
+}
+
+#[test]
+fn trivia_is_off_by_default() {
+    let lexes: Vec<Lex> = Lexer::new(" a ; b\n".chars()).map(|l| l.unwrap()).collect();
+    assert_eq!(lexes.len(), 1);
+    assert!(lexes[0].leading().is_empty() && lexes[0].trailing().is_empty());
+}
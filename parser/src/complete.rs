@@ -0,0 +1,43 @@
+/* parser/src/complete.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # Completeness
+//!
+//! A REPL reads input a line at a time, and has to decide after each line whether it has
+//! something to evaluate or should prompt for more. `check` answers that for the input read so
+//! far: either it's all whole datums, or it ends partway through one, or it has an error that
+//! more input won't fix.
+
+use sibillexer::Lexer;
+use sibiltypes::Obj;
+use error::ParseError;
+use Parser;
+
+/// What `check` found in its input.
+#[derive(Debug, PartialEq)]
+pub enum Completeness {
+    /// The input is all whole datums, or nothing but whitespace and comments.
+    Complete(Vec<Obj>),
+    /// The input ends partway through a datum. `datums` are the whole datums before it, and
+    /// `depth` is how many lists, vectors and bytevectors are open at the end of the input.
+    Incomplete { datums: Vec<Obj>, depth: usize },
+    /// The input has an error that more input can't fix.
+    Error(ParseError),
+}
+
+/// Check whether `input` is ready to be evaluated.
+pub fn check(input: &str) -> Completeness {
+    let mut parser = Parser::new(Lexer::new(input.chars()));
+    let mut datums = Vec::new();
+    while let Some(result) = parser.next() {
+        match result {
+            Ok(obj) => datums.push(obj),
+            Err(ref err) if err.is_incomplete() => {
+                return Completeness::Incomplete { datums, depth: parser.depth() };
+            },
+            Err(err) => return Completeness::Error(err),
+        }
+    }
+    Completeness::Complete(datums)
+}
@@ -0,0 +1,98 @@
+/* parser/src/error.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use std::error;
+use std::fmt;
+use std::rc::Rc;
+use sibillexer::{ErrorKind, Lex, Span};
+use sibillexer::Error as LexerError;
+
+/// An error found while parsing. Every error says where in the input it is.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// The lexer couldn't make a token.
+    Lexer(LexerError),
+    /// The input ended before the list, vector or bytevector opened at `open` was closed.
+    Unclosed { open: Span },
+    /// A `)` that doesn't close anything.
+    Unopened { close: Span },
+    /// A `.` that isn't between the last two datums of a list.
+    MisplacedDot { dot: Span },
+    /// A token that can't appear where it was found. `found` is the text of the token.
+    Unexpected { expected: &'static str, found: String, span: Span },
+    /// The input ended where something was expected, after the unfinished prefix at `after`, e.g.
+    /// a quote.
+    UnexpectedEof { expected: &'static str, after: Span },
+    /// Anything else wrong with the datum at `span`, e.g. a byte that's out of range or a datum
+    /// label that was never defined.
+    Invalid { msg: String, span: Span },
+}
+
+impl ParseError {
+    /// `lex` isn't what was expected.
+    pub fn unexpected(expected: &'static str, lex: &Lex) -> ParseError {
+        ParseError::Unexpected { expected, found: lex.value().to_string(), span: lex.span().clone() }
+    }
+
+    pub fn invalid(msg: String, span: &Span) -> ParseError {
+        ParseError::Invalid { msg, span: span.clone() }
+    }
+
+    /// True if the error is that the input ended too soon, so more input could fix it.
+    pub fn is_incomplete(&self) -> bool {
+        match *self {
+            ParseError::Lexer(ref err) => matches!(err.kind(),
+                ErrorKind::UnexpectedEof | ErrorKind::UnterminatedString | ErrorKind::NeedMoreInput),
+            ParseError::Unclosed { .. } | ParseError::UnexpectedEof { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Where in the input the error is. A lexer error is at a single position, so its span is
+    /// empty.
+    pub fn span(&self) -> Span {
+        match *self {
+            ParseError::Lexer(ref err) => {
+                Span::new(err.pos(), err.pos()).in_file(err.file().map(Rc::from))
+            },
+            ParseError::Unclosed { ref open } => open.clone(),
+            ParseError::Unopened { ref close } => close.clone(),
+            ParseError::MisplacedDot { ref dot } => dot.clone(),
+            ParseError::Unexpected { ref span, .. } => span.clone(),
+            ParseError::UnexpectedEof { ref after, .. } => after.clone(),
+            ParseError::Invalid { ref span, .. } => span.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Lexer(ref err) => write!(f, "{}", err),
+            ParseError::Unclosed { ref open } => write!(f, "{}: unclosed paren", open),
+            ParseError::Unopened { ref close } => write!(f, "{}: unexpected closing paren", close),
+            ParseError::MisplacedDot { ref dot } => write!(f, "{}: misplaced dot", dot),
+            ParseError::Unexpected { expected, ref found, ref span } =>
+                write!(f, "{}: expected {}, found {}", span, expected, found),
+            ParseError::UnexpectedEof { expected, ref after } =>
+                write!(f, "{}: expected {} after this, found end of input", after, expected),
+            ParseError::Invalid { ref msg, ref span } => write!(f, "{}: {}", span, msg),
+        }
+    }
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            ParseError::Lexer(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<LexerError> for ParseError {
+    fn from(err: LexerError) -> ParseError {
+        ParseError::Lexer(err)
+    }
+}
@@ -0,0 +1,120 @@
+/* parser/src/labels.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # Datum Labels
+//!
+//! A datum can be labeled with `#n=` and referred to later with `#n#`, which makes the reference
+//! the very same object as the labeled datum. A reference inside the datum it refers to makes a
+//! cycle. Since that datum isn't finished yet, the reference gets a placeholder, and once the
+//! datum is done the placeholder is replaced by it everywhere it appears. Labels are only good
+//! within the top-level datum they appear in.
+
+use std::collections::{HashMap, HashSet};
+use sibiltypes::{Obj, Sym};
+use spans;
+
+#[derive(Debug, Default)]
+pub struct Labels {
+    labels: HashMap<usize, Label>,
+}
+
+#[derive(Debug)]
+enum Label {
+    /// The labeled datum is being parsed by the parser `depth` deep in the parser stack. The
+    /// placeholder stands in for it until it's done.
+    Pending { depth: usize, placeholder: Obj },
+    Done(Obj),
+}
+
+impl Labels {
+    pub fn new() -> Labels {
+        Labels { labels: HashMap::new() }
+    }
+
+    pub fn clear(&mut self) {
+        self.labels.clear();
+    }
+
+    /// Start the datum labeled `label`, which is parsed by the parser `depth` deep in the stack.
+    pub fn define(&mut self, label: usize, depth: usize) -> Result<(), String> {
+        if self.labels.contains_key(&label) {
+            return Err(format!("datum label defined twice: #{}=", label));
+        }
+        // The placeholder only needs to be a unique object, so any object will do.
+        let placeholder = Obj::new(Sym::new(format!("#{}#", label)));
+        self.labels.insert(label, Label::Pending { depth, placeholder });
+        Ok(())
+    }
+
+    /// The object a `#label#` reference refers to, if the label has been defined.
+    pub fn get(&self, label: usize) -> Option<Obj> {
+        self.labels.get(&label).map(|l| match *l {
+            Label::Pending { ref placeholder, .. } => placeholder.clone(),
+            Label::Done(ref obj) => obj.clone(),
+        })
+    }
+
+    /// The parser `depth` deep in the stack completed with `obj`. If that parser was parsing a
+    /// labeled datum, `obj` is the datum, so fill it in for the references to it.
+    pub fn complete(&mut self, depth: usize, obj: &Obj) -> Result<(), String> {
+        let pending = self.labels.iter()
+            .filter_map(|(&label, l)| match *l {
+                Label::Pending { depth: d, ref placeholder } if d == depth => Some((label, placeholder.clone())),
+                _ => None,
+            })
+            .next();
+        let (label, placeholder) = match pending {
+            Some(pending) => pending,
+            None => return Ok(()),
+        };
+        if obj.ptr_eq(&placeholder) {
+            return Err(format!("datum label #{}= refers only to itself", label));
+        }
+        replace(obj, &placeholder, obj);
+        self.labels.insert(label, Label::Done(obj.clone()));
+        Ok(())
+    }
+
+    /// True if `obj` is a labeled datum or a placeholder for one.
+    pub fn contains(&self, obj: &Obj) -> bool {
+        self.labels.values().any(|l| match *l {
+            Label::Pending { ref placeholder, .. } => placeholder.ptr_eq(obj),
+            Label::Done(ref done) => done.ptr_eq(obj),
+        })
+    }
+}
+
+/// Replace `placeholder` with `obj` everywhere it appears in the pairs and vectors reachable from
+/// `root`.
+fn replace(root: &Obj, placeholder: &Obj, obj: &Obj) {
+    let mut seen = HashSet::new();
+    let mut stack = vec![root.clone()];
+    while let Some(next) = stack.pop() {
+        match spans::key(&next) {
+            Some(k) if seen.insert(k) => {},
+            _ => continue,
+        }
+        let object = next.obj().unwrap();
+        if let Some(pair) = object.as_pair() {
+            if pair.car().ptr_eq(placeholder) {
+                pair.set_car(obj.clone());
+            } else {
+                stack.push(pair.car());
+            }
+            if pair.cdr().ptr_eq(placeholder) {
+                pair.set_cdr(obj.clone());
+            } else {
+                stack.push(pair.cdr());
+            }
+        } else if let Some(vector) = object.as_vector() {
+            for (i, item) in vector.iter().enumerate() {
+                if item.ptr_eq(placeholder) {
+                    vector.set(i, obj.clone());
+                } else {
+                    stack.push(item);
+                }
+            }
+        }
+    }
+}
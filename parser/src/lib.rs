@@ -5,39 +5,96 @@
 extern crate sibillexer;
 extern crate sibiltypes;
 
+pub mod complete;
+mod error;
+mod labels;
 mod parsers;
+pub mod spans;
+pub mod trace;
 
 use std::iter::Peekable;
 use sibillexer::Result as LexerResult;
-use sibillexer::Lex;
+use sibillexer::{Lex, Span, Token};
 use sibiltypes::Obj;
 use parsers::{NodeParser, NodeParseResult};
 use parsers::ProgramParser;
+use labels::Labels;
+use spans::Spans;
+use trace::{Event, Trace};
+
+pub use error::ParseError;
 
 /// The output of calling `parse()` on a Parser is one of these Result objects.
 pub type Result = std::result::Result<Obj, ParseError>;
 
-#[derive(Debug, PartialEq)]
-pub enum ParseError {
-    LexerError{msg: String},
-    ParserError{msg: String}
-}
-
 pub struct Parser<T> where T: Iterator<Item=LexerResult> {
     input: Peekable<T>,
     parsers: Vec<Box<NodeParser>>,
+    /// The span of the Lex each parser on the stack started with.
+    starts: Vec<Option<Span>>,
+    /// The span of the most recently read Lex.
+    current: Option<Span>,
+    /// Identity and span of the innermost parser to complete since the last Lex was read. Parsers
+    /// that pass along the object their subparser made don't replace its span.
+    completed: Option<(Option<usize>, Span)>,
+    spans: Spans,
+    last_span: Option<Span>,
+    /// The datum labels defined in the current top-level datum.
+    labels: Labels,
+    /// Where to report what the parser is doing, if anywhere.
+    trace: Option<Box<Trace>>,
+    /// How many lists, vectors and bytevectors are open at the most recently read Lex. After an
+    /// error, the parser skips ahead until this is back to zero, so parsing picks up again at the
+    /// next top-level datum.
+    depth: usize,
 }
 
 impl<T> Parser<T> where T: Iterator<Item=LexerResult> {
     pub fn new(input: T) -> Parser<T> {
         Parser {
             input: input.peekable(),
-            parsers: vec![]
+            parsers: vec![],
+            starts: vec![],
+            current: None,
+            completed: None,
+            spans: Spans::new(),
+            last_span: None,
+            labels: Labels::new(),
+            trace: None,
+            depth: 0,
+        }
+    }
+
+    /// Where in the input each datum this parser has produced came from, including the datums
+    /// nested in lists and vectors.
+    pub fn spans(&self) -> &Spans {
+        &self.spans
+    }
+
+    /// The span of the last top-level datum this parser produced.
+    pub fn last_span(&self) -> Option<&Span> {
+        self.last_span.as_ref()
+    }
+
+    /// How many lists, vectors and bytevectors are open at the most recently read Lex.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Report what the parser is doing to `sink`. See the `trace` module.
+    pub fn set_trace<S>(&mut self, sink: S) where S: Trace + 'static {
+        self.trace = Some(Box::new(sink));
+    }
+
+    fn trace(&mut self, event: Event) {
+        if let Some(ref mut sink) = self.trace {
+            sink.trace(&event);
         }
     }
 
     fn prepare(&mut self) {
         assert_eq!(self.parsers.len(), 0);
+        self.labels.clear();
         let program_parser = Box::new(ProgramParser::new());
         self.push_parser(program_parser);
     }
@@ -53,20 +110,85 @@ impl<T> Parser<T> where T: Iterator<Item=LexerResult> {
     }
 
     fn pop_parser(&mut self) {
-        self.parsers.pop();
-        println!("popped parser stack --> {:?}", self.parsers);
+        self.starts.pop();
+        if let Some(parser) = self.parsers.pop() {
+            let depth = self.parsers.len();
+            self.trace(Event::Pop { parser: parser.name(), depth });
+        }
     }
 
     fn push_parser(&mut self, next: Box<NodeParser>) {
+        let parser = next.name();
         self.parsers.push(next);
-        println!("pushed onto parser stack -> {:?}", self.parsers);
+        self.starts.push(self.current.clone());
+        self.completed = None;
+        let depth = self.parsers.len();
+        self.trace(Event::Push { parser, depth });
     }
 
     fn next_lex(&mut self) -> Option<T::Item> {
-        let next = self.input.next();
-        println!("next lex: {:?}", next);
+        let next = match self.input.next() {
+            // A lexer that keeps trivia marks the end of its input with a token.
+            Some(Ok(ref lex)) if *lex.token() == Token::Eof => None,
+            next => next,
+        };
+        self.completed = None;
+        if let Some(Ok(ref lex)) = next {
+            self.current = Some(lex.span().clone());
+            match *lex.token() {
+                Token::LeftParen | Token::VectorStart | Token::ByteVectorStart => self.depth += 1,
+                Token::RightParen => self.depth = self.depth.saturating_sub(1),
+                _ => {},
+            }
+        }
+        match next {
+            Some(ref lex) => self.trace(Event::Read(lex)),
+            None => self.trace(Event::End),
+        }
         next
     }
+
+    fn clear_parsers(&mut self) {
+        self.parsers.clear();
+        self.starts.clear();
+    }
+
+    /// Skip the rest of the datum an error happened in. Lexer errors in the skipped input aren't
+    /// reported; they're part of the datum that's already broken.
+    fn skip_broken_form(&mut self) {
+        while self.depth > 0 {
+            if self.next_lex().is_none() {
+                break;
+            }
+        }
+        self.depth = 0;
+    }
+
+    /// An error about the most recently read Lex.
+    fn invalid(&self, msg: String) -> NodeParseResult {
+        let span = self.current.as_ref().expect("no Lex has been read");
+        NodeParseResult::error(ParseError::invalid(msg, span))
+    }
+
+    /// Record the span of `obj`, which the parser on top of the stack just completed with.
+    fn record_span(&mut self, obj: &Obj) {
+        // A reference to a labeled datum doesn't move the datum.
+        if self.labels.contains(obj) {
+            return;
+        }
+        let key = spans::key(obj);
+        if let Some((completed, _)) = self.completed {
+            if completed == key {
+                return;
+            }
+        }
+        let start = self.starts.last().and_then(|s| s.as_ref());
+        if let (Some(start), Some(end)) = (start, self.current.as_ref()) {
+            let span = start.to(end);
+            self.spans.insert(obj, span.clone());
+            self.completed = Some((key, span));
+        }
+    }
 }
 
 impl<T> Iterator for Parser<T> where T: Iterator<Item=LexerResult> {
@@ -76,6 +198,7 @@ impl<T> Iterator for Parser<T> where T: Iterator<Item=LexerResult> {
         let out: Option<Self::Item>;
         let mut result: Option<NodeParseResult> = None;
         let mut input_lex: Option<T::Item> = None;
+        self.skip_broken_form();
         loop {
             input_lex = match result {
                 None => {
@@ -92,20 +215,34 @@ impl<T> Iterator for Parser<T> where T: Iterator<Item=LexerResult> {
                 }
                 Some(NodeParseResult::Continue) => self.next_lex(),
                 Some(NodeParseResult::Complete{ obj }) => {
-                    println!("{:?} completed with {:?}", self.parsers.last().unwrap(), obj);
+                    let parser = self.parsers.last().unwrap().name();
+                    self.trace(Event::Complete { parser, obj: &obj });
+                    self.record_span(&obj);
+                    if let Err(msg) = self.labels.complete(self.parsers.len(), &obj) {
+                        result = Some(self.invalid(msg));
+                        continue;
+                    }
                     self.pop_parser();
                     if self.parsers.len() == 0 {
-                        println!("we are done parsing");
+                        self.last_span = self.completed.take().map(|(_, span)| span);
                         out = Some(Ok(obj));
                         break;
-                    } else {
-                        let prev_parser = self.parsers.last_mut().unwrap();
-                        println!("passing completed obj {:?} to previous parser {:?}", obj, prev_parser);
-                        result = Some(prev_parser.subparser_completed(obj));
-                        continue;
                     }
-                    // TODO: This is dead code now... Is this correct?
-                    println!("parsers {:?}", self.parsers);
+                    let prev_parser = self.parsers.last_mut().unwrap();
+                    result = Some(prev_parser.subparser_completed(obj));
+                    continue;
+                },
+                Some(NodeParseResult::Discard) => {
+                    self.pop_parser();
+                    if self.parsers.is_empty() {
+                        out = None;
+                        break;
+                    }
+                    if self.parsers.len() == 1 {
+                        // That was a top-level datum comment. Its labels were scoped to it, like
+                        // any other top-level datum's.
+                        self.labels.clear();
+                    }
                     self.next_lex()
                 },
                 Some(NodeParseResult::Push{ next }) => {
@@ -113,8 +250,23 @@ impl<T> Iterator for Parser<T> where T: Iterator<Item=LexerResult> {
                     self.push_parser(next);
                     input_lex
                 },
-                Some(NodeParseResult::Error{ msg }) => {
-                    out = Some(Err(ParseError::ParserError{ msg }));
+                Some(NodeParseResult::Label{ label }) => {
+                    if let Err(msg) = self.labels.define(label, self.parsers.len()) {
+                        result = Some(self.invalid(msg));
+                        continue;
+                    }
+                    self.next_lex()
+                },
+                Some(NodeParseResult::Reference{ label }) => {
+                    result = Some(match self.labels.get(label) {
+                        Some(obj) => NodeParseResult::Complete { obj },
+                        None => self.invalid(format!("undefined datum label: #{}#", label)),
+                    });
+                    continue;
+                },
+                Some(NodeParseResult::Error{ error }) => {
+                    self.clear_parsers();
+                    out = Some(Err(error));
                     break;
                 }
             };
@@ -125,8 +277,8 @@ impl<T> Iterator for Parser<T> where T: Iterator<Item=LexerResult> {
                 },
                 Some(Err(ref error)) => {
                     // Lexer error. Throw it up and out.
-                    let msg = error.msg().to_string();
-                    out = Some(Err(ParseError::LexerError { msg }));
+                    self.clear_parsers();
+                    out = Some(Err(ParseError::Lexer(error.clone())));
                     break;
                 },
                 None => {
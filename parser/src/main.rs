@@ -2,35 +2,44 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
-extern crate sibillexer;
 extern crate sibilparser;
 
 use std::io::prelude::*;
 use std::io;
-use sibillexer::Lexer;
-use sibilparser::Parser;
+use sibilparser::complete::{check, Completeness};
 
-fn main() {
-    loop {
-        // Print a prompt.
-        print!("> ");
-        io::stdout().flush().ok().expect("couldn't flush");
-
-        // Read a line from stdin.
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
-
-        // Remove the trailing newline.
-        input.pop();
-
-        // Create a lexer and parser and process the input.
-        let lexer = Lexer::new(input.chars());
-        let parser = Parser::new(lexer);
+fn prompt(depth: Option<usize>) {
+    match depth {
+        None => print!("> "),
+        // Indent continuation lines by how deep the unfinished datum is.
+        Some(depth) => print!("..{} ", "  ".repeat(depth)),
+    }
+    io::stdout().flush().expect("couldn't flush");
+}
 
-        // Print the parser's output.
-        for thing in parser {
-            println!("{:?}", thing);
+fn main() {
+    // Read a line at a time, and hold on to the lines until they make whole datums.
+    let stdin = io::stdin();
+    let mut input = String::new();
+    prompt(None);
+    for line in stdin.lock().lines() {
+        input.push_str(&line.expect("couldn't read a line"));
+        input.push('\n');
+        match check(&input) {
+            Completeness::Complete(datums) => {
+                for datum in datums {
+                    println!("{}", datum);
+                }
+                input.clear();
+                prompt(None);
+            },
+            Completeness::Incomplete { depth, .. } => prompt(Some(depth)),
+            Completeness::Error(err) => {
+                println!("error: {}", err);
+                input.clear();
+                prompt(None);
+            },
         }
-        println!();
     }
+    println!();
 }
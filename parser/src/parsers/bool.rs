@@ -4,6 +4,7 @@
 
 use sibillexer::{Lex, Token};
 use sibiltypes::{Bool, Obj};
+use error::ParseError;
 use parsers::{NodeParser, NodeParseResult};
 
 #[derive(Debug)] pub struct BoolParser;
@@ -12,22 +13,17 @@ impl NodeParser for BoolParser {
     fn parse(&mut self, lex: &Lex) -> NodeParseResult {
         match lex.token() {
             Token::Bool(value) => {
-                NodeParseResult::Complete { obj: Obj::new(Bool::from(value)) }
-            }
-            _ => {
-                let msg = format!("Expected bool, found {:?}", lex);
-                NodeParseResult::error(msg)
+                NodeParseResult::Complete { obj: Obj::new(Bool::from(*value)) }
             }
+            _ => NodeParseResult::error(ParseError::unexpected("boolean", lex)),
         }
     }
 
     fn none(&mut self) -> NodeParseResult {
-        let msg = format!("Expected bool, found EOF");
-        NodeParseResult::error(msg)
+        unreachable!("BoolParser completes on the first Lex it sees")
     }
 
-    fn subparser_completed(&mut self, obj: Obj) -> NodeParseResult {
-        let msg = format!("Unexpected subparser result: {}", obj);
-        NodeParseResult::error(msg)
+    fn subparser_completed(&mut self, _obj: Obj) -> NodeParseResult {
+        unreachable!("BoolParser doesn't push subparsers")
     }
 }
@@ -0,0 +1,27 @@
+/* parser/src/parsers/char.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use sibillexer::{Lex, Token};
+use sibiltypes::{Char, Obj};
+use error::ParseError;
+use parsers::{NodeParser, NodeParseResult};
+
+#[derive(Debug)] pub struct CharParser;
+
+impl NodeParser for CharParser {
+    fn parse(&mut self, lex: &Lex) -> NodeParseResult {
+        match *lex.token() {
+            Token::Char(c) => NodeParseResult::Complete { obj: Obj::new(Char(c)) },
+            _ => NodeParseResult::error(ParseError::unexpected("character", lex)),
+        }
+    }
+
+    fn none(&mut self) -> NodeParseResult {
+        unreachable!("CharParser completes on the first Lex it sees")
+    }
+
+    fn subparser_completed(&mut self, _obj: Obj) -> NodeParseResult {
+        unreachable!("CharParser doesn't push subparsers")
+    }
+}
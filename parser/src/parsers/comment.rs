@@ -0,0 +1,46 @@
+/* parser/src/parsers/comment.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use sibillexer::{Lex, Span, Token};
+use sibiltypes::Obj;
+use error::ParseError;
+use parsers::{datum_parser, not_a_datum, NodeParser, NodeParseResult};
+
+/// Parses a `#;` datum comment and the datum following it, and then throws the datum away.
+#[derive(Debug)]
+pub struct DatumCommentParser {
+    /// The span of the `#;`, once it's been seen.
+    prefix: Option<Span>,
+}
+
+impl DatumCommentParser {
+    pub fn new() -> DatumCommentParser {
+        DatumCommentParser { prefix: None }
+    }
+}
+
+impl NodeParser for DatumCommentParser {
+    fn parse(&mut self, lex: &Lex) -> NodeParseResult {
+        match lex.token() {
+            Token::DatumComment if self.prefix.is_none() => {
+                self.prefix = Some(lex.span().clone());
+                NodeParseResult::Continue
+            },
+            _ if self.prefix.is_some() => match datum_parser(lex) {
+                Some(next) => NodeParseResult::Push { next },
+                None => NodeParseResult::error(not_a_datum(lex)),
+            },
+            _ => NodeParseResult::error(ParseError::unexpected("datum comment", lex)),
+        }
+    }
+
+    fn none(&mut self) -> NodeParseResult {
+        let after = self.prefix.clone().expect("DatumCommentParser starts with a Lex");
+        NodeParseResult::error(ParseError::UnexpectedEof { expected: "datum", after })
+    }
+
+    fn subparser_completed(&mut self, _obj: Obj) -> NodeParseResult {
+        NodeParseResult::Discard
+    }
+}
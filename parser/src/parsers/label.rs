@@ -0,0 +1,68 @@
+/* parser/src/parsers/label.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use sibillexer::{Lex, Span, Token};
+use sibiltypes::Obj;
+use error::ParseError;
+use parsers::{datum_parser, not_a_datum, NodeParser, NodeParseResult};
+
+/// Parses a `#n=` datum label and the datum following it. The result is the datum, which the
+/// `Parser` remembers so `#n#` can refer to it.
+#[derive(Debug)]
+pub struct DatumLabelParser {
+    /// The span of the label, once it's been seen.
+    label: Option<Span>,
+}
+
+/// Parses a `#n#` reference to a labeled datum. The `Parser` looks up the datum.
+#[derive(Debug)]
+pub struct DatumRefParser;
+
+impl DatumLabelParser {
+    pub fn new() -> DatumLabelParser {
+        DatumLabelParser { label: None }
+    }
+}
+
+impl NodeParser for DatumLabelParser {
+    fn parse(&mut self, lex: &Lex) -> NodeParseResult {
+        match (&self.label, lex.token()) {
+            (&None, &Token::DatumLabel(label)) => {
+                self.label = Some(lex.span().clone());
+                NodeParseResult::Label { label }
+            },
+            (&None, _) => NodeParseResult::error(ParseError::unexpected("datum label", lex)),
+            (&Some(_), _) => match datum_parser(lex) {
+                Some(next) => NodeParseResult::Push { next },
+                None => NodeParseResult::error(not_a_datum(lex)),
+            },
+        }
+    }
+
+    fn none(&mut self) -> NodeParseResult {
+        let after = self.label.clone().expect("DatumLabelParser starts with a Lex");
+        NodeParseResult::error(ParseError::UnexpectedEof { expected: "datum", after })
+    }
+
+    fn subparser_completed(&mut self, obj: Obj) -> NodeParseResult {
+        NodeParseResult::Complete { obj }
+    }
+}
+
+impl NodeParser for DatumRefParser {
+    fn parse(&mut self, lex: &Lex) -> NodeParseResult {
+        match *lex.token() {
+            Token::DatumRef(label) => NodeParseResult::Reference { label },
+            _ => NodeParseResult::error(ParseError::unexpected("datum reference", lex)),
+        }
+    }
+
+    fn none(&mut self) -> NodeParseResult {
+        unreachable!("DatumRefParser completes on the first Lex it sees")
+    }
+
+    fn subparser_completed(&mut self, _obj: Obj) -> NodeParseResult {
+        unreachable!("DatumRefParser doesn't push subparsers")
+    }
+}
@@ -2,120 +2,102 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
-use sibillexer::{Lex, Token};
+use sibillexer::{Lex, Span, Token};
 use sibiltypes::{Obj, Pair};
-use parsers::{NodeParser, NodeParseResult};
-use parsers::bool::BoolParser;
-use parsers::sym::SymParser;
+use error::ParseError;
+use parsers::{datum_parser, not_a_datum, NodeParser, NodeParseResult};
+use parsers::comment::DatumCommentParser;
 
 #[derive(Debug)]
 pub struct ListParser {
     pairs: Option<Vec<Pair>>,
-    waiting_for_final: bool,
+    /// The span of the opening paren, once it's been seen.
+    open: Option<Span>,
+    tail: Tail,
+}
+
+/// How far along the dotted tail of a list the parser is.
+#[derive(Debug)]
+enum Tail {
+    /// No dot yet.
+    None,
+    /// After the dot, waiting for the datum that ends the list.
+    Dot,
+    /// After the datum that ends the list, waiting for the closing paren.
+    Final,
 }
 
 impl ListParser {
     pub fn new() -> ListParser {
         ListParser {
             pairs: None,
-            waiting_for_final: false,
+            open: None,
+            tail: Tail::None,
         }
     }
 
-    fn assemble(&mut self) -> Result<Obj, String> {
-        match self.pairs.take() {
-            Some(mut pairs) => {
-                let last = pairs.last_mut().and_then(|p| Some(p.cdr.take())).unwrap_or(Obj::Null);
-                let obj = pairs.into_iter().rfold(last, |acc, mut pair| {
-                    pair.cdr = acc;
-                    Obj::new(pair)
-                });
-                Ok(obj)
-            },
-            None => Err("bad".to_string())
-        }
+    fn assemble(&mut self) -> Obj {
+        let pairs = self.pairs.take().unwrap_or_default();
+        let last = pairs.last().map_or(Obj::Null, Pair::cdr);
+        pairs.into_iter().rfold(last, |acc, pair| {
+            pair.set_cdr(acc);
+            Obj::new(pair)
+        })
     }
 }
 
 impl NodeParser for ListParser {
     fn parse(&mut self, lex: &Lex) -> NodeParseResult {
-        match lex.token() {
-            Token::Bool(_) => {
-                let parser = BoolParser{};
-                NodeParseResult::Push { next: Box::new(parser) }
+        let pairs = match self.pairs {
+            Some(ref pairs) => pairs,
+            None => return match lex.token() {
+                Token::LeftParen => {
+                    // Create our empty pair and proceed parsing this list.
+                    self.pairs = Some(Vec::new());
+                    self.open = Some(lex.span().clone());
+                    NodeParseResult::Continue
+                },
+                _ => NodeParseResult::error(ParseError::unexpected("list", lex)),
             },
-            Token::Dot => {
-                self.waiting_for_final = true;
+        };
+        match (&self.tail, lex.token()) {
+            (_, Token::Eof) => self.none(),
+            // Datum comments can go anywhere, even between the dot and the closing paren.
+            (_, Token::DatumComment) => NodeParseResult::Push { next: Box::new(DatumCommentParser::new()) },
+            // A dot has to follow at least one datum, and there can only be one.
+            (&Tail::None, Token::Dot) if !pairs.is_empty() => {
+                self.tail = Tail::Dot;
                 NodeParseResult::Continue
             },
-            Token::LeftParen => {
-                match self.pairs {
-                    None => {
-                        // Create our empty pair and proceed parsing this list.
-                        self.pairs = Some(Vec::new());
-                        NodeParseResult::Continue
-                    },
-                    Some(_) => {
-                        // This is an embedded list. Create a new parser for it.
-                        let next = Box::new(ListParser::new());
-                        NodeParseResult::Push { next }
-                    }
-                }
-            },
-            Token::Id => {
-                let next = Box::new(SymParser{});
-                NodeParseResult::Push { next }
+            (_, Token::Dot) => NodeParseResult::error(ParseError::MisplacedDot { dot: lex.span().clone() }),
+            (&Tail::Dot, Token::RightParen) => {
+                NodeParseResult::error(ParseError::unexpected("datum after dot", lex))
             },
-            Token::Num(n) => {
-                panic!("TODO: Handle numbrs.");
-            },
-            Token::Quote => {
-                panic!("TODO: Handle quotes.");
-            },
-            Token::RightParen => {
-                match self.pairs {
-                    None => {
-                        let msg = format!("Found right paren without matching left paren");
-                        NodeParseResult::error(msg)
-                    },
-                    Some(_) => {
-                        let list = self.assemble();
-                        match list {
-                            Ok(list) => NodeParseResult::Complete { obj: list },
-                            Err(msg) => NodeParseResult::error(msg)
-                        }
-                    }
-                }
+            (_, Token::RightParen) => NodeParseResult::Complete { obj: self.assemble() },
+            (&Tail::Final, _) => NodeParseResult::error(ParseError::unexpected("closing paren", lex)),
+            (_, _) => match datum_parser(lex) {
+                Some(next) => NodeParseResult::Push { next },
+                None => NodeParseResult::error(not_a_datum(lex)),
             },
         }
     }
 
     fn none(&mut self) -> NodeParseResult {
-        let msg = format!("Unmatched paren, found EOF");
-        NodeParseResult::error(msg)
+        let open = self.open.clone().expect("ListParser starts with a Lex");
+        NodeParseResult::error(ParseError::Unclosed { open })
     }
 
     fn subparser_completed(&mut self, obj: Obj) -> NodeParseResult {
-        match self.pairs {
-            Some(ref mut pairs) if self.waiting_for_final => match pairs.last_mut() {
-                Some(ref mut last) => {
-                    last.cdr = obj;
-                    // Waiting for RightParen to close list.
-                    NodeParseResult::Continue
-                },
-                None => {
-                    let msg = "Found dot before any pairs parsed".to_string();
-                    NodeParseResult::error(msg)
-                },
-            },
-            Some(ref mut pairs) => {
-                pairs.push(Pair::with_car(obj));
-                NodeParseResult::Continue
-            },
-            None => {
-                let msg = "While attempting to parse list, found token before opening paren".to_string();
-                NodeParseResult::error(msg)
+        let pairs = self.pairs.as_mut().expect("ListParser pushes subparsers after its opening paren");
+        match self.tail {
+            Tail::None => pairs.push(Pair::with_car(obj)),
+            Tail::Dot => {
+                // The parser doesn't take a dot until there's a pair for it to end.
+                pairs.last().expect("a dot follows at least one datum").set_cdr(obj);
+                self.tail = Tail::Final;
             },
+            Tail::Final => unreachable!("ListParser doesn't push subparsers after its final datum"),
         }
+        NodeParseResult::Continue
     }
 }
@@ -3,15 +3,34 @@
  */
 
 mod bool;
+mod char;
+mod comment;
+mod label;
 mod list;
+mod num;
 mod program;
+mod quote;
+mod string;
 mod sym;
+mod vector;
 
 pub use self::program::ProgramParser;
 
+use std::any::type_name;
 use std::fmt::Debug;
-use sibillexer::Lex;
+use sibillexer::{Lex, Token};
 use sibiltypes::Obj;
+use error::ParseError;
+use self::bool::BoolParser;
+use self::char::CharParser;
+use self::comment::DatumCommentParser;
+use self::label::{DatumLabelParser, DatumRefParser};
+use self::list::ListParser;
+use self::num::NumParser;
+use self::quote::QuoteParser;
+use self::string::StrParser;
+use self::sym::SymParser;
+use self::vector::VectorParser;
 
 #[derive(Debug)]
 pub enum NodeParseResult {
@@ -19,15 +38,23 @@ pub enum NodeParseResult {
     Continue,
     /// This NodeParser has completed its work and has produced the given Object as a result.
     Complete { obj: Obj },
+    /// This NodeParser has completed its work and has nothing to show for it. Parsing should
+    /// continue with the previous NodeParser.
+    Discard,
     /// Push a new NodeParser onto the parsing stack and let that parser proceed with the current Lex.
     Push { next: Box<NodeParser> },
+    /// This NodeParser is parsing the datum labeled `label`. The object it completes with is that
+    /// datum.
+    Label { label: usize },
+    /// This NodeParser is done, and its result is the datum labeled `label`.
+    Reference { label: usize },
     /// There was an error parsing with the current Lex.
-    Error { msg: String },
+    Error { error: ParseError },
 }
 
 impl NodeParseResult {
-    pub fn error(msg: String) -> NodeParseResult {
-        NodeParseResult::Error { msg: msg }
+    pub fn error(error: ParseError) -> NodeParseResult {
+        NodeParseResult::Error { error }
     }
 }
 
@@ -35,7 +62,7 @@ impl NodeParseResult {
 /// Roughly, there should be one NodeParser for each type of object in `sibiltypes`. As the
 /// top-level `Parser` object progresses through the stream of tokens, new NodeParsers are created
 /// to handle the nodes it encounters.
-pub trait NodeParser: Debug {
+pub trait NodeParser: Debug + Named {
     /// Called on a NodeParser when a Lex is encountered in the input.
     fn parse(&mut self, lex: &Lex) -> NodeParseResult;
 
@@ -46,3 +73,45 @@ pub trait NodeParser: Debug {
     /// `NodeParseResult::Complete`.
     fn subparser_completed(&mut self, obj: Obj) -> NodeParseResult;
 }
+
+/// Provides the name of a NodeParser for tracing. This is implemented for every NodeParser, so
+/// there's no need to implement it by hand.
+pub trait Named {
+    fn name(&self) -> &'static str;
+}
+
+impl<T> Named for T where T: NodeParser {
+    fn name(&self) -> &'static str {
+        let name = type_name::<T>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+}
+
+/// Create a NodeParser for the datum that begins with the given Lex. Returns `None` if no datum
+/// can begin with that Lex.
+pub fn datum_parser(lex: &Lex) -> Option<Box<NodeParser>> {
+    match lex.token() {
+        Token::Bool(_) => Some(Box::new(BoolParser{})),
+        Token::Char(_) => Some(Box::new(CharParser{})),
+        Token::DatumComment => Some(Box::new(DatumCommentParser::new())),
+        Token::DatumLabel(_) => Some(Box::new(DatumLabelParser::new())),
+        Token::DatumRef(_) => Some(Box::new(DatumRefParser{})),
+        Token::Id(_) => Some(Box::new(SymParser{})),
+        Token::LeftParen => Some(Box::new(ListParser::new())),
+        Token::Num(_) | Token::Complex(..) => Some(Box::new(NumParser{})),
+        Token::Quote | Token::Quasiquote | Token::Unquote | Token::UnquoteSplicing =>
+            Some(Box::new(QuoteParser::new())),
+        Token::Str(_) => Some(Box::new(StrParser{})),
+        Token::VectorStart | Token::ByteVectorStart => Some(Box::new(VectorParser::new())),
+        _ => None
+    }
+}
+
+/// The error for a Lex that `datum_parser` couldn't make a NodeParser for.
+pub fn not_a_datum(lex: &Lex) -> ParseError {
+    match lex.token() {
+        Token::Dot => ParseError::MisplacedDot { dot: lex.span().clone() },
+        Token::RightParen => ParseError::Unopened { close: lex.span().clone() },
+        _ => ParseError::unexpected("datum", lex),
+    }
+}
@@ -0,0 +1,33 @@
+/* parser/src/parsers/num.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use sibillexer::{Lex, Num, Token};
+use sibiltypes::Obj;
+use error::ParseError;
+use parsers::{NodeParser, NodeParseResult};
+
+#[derive(Debug)] pub struct NumParser;
+
+impl NodeParser for NumParser {
+    fn parse(&mut self, lex: &Lex) -> NodeParseResult {
+        match *lex.token() {
+            Token::Num(Num::Int(n)) => NodeParseResult::Complete { obj: Obj::new(n) },
+            Token::Num(Num::Frac(n)) => NodeParseResult::Complete { obj: Obj::new(n) },
+            Token::Num(Num::Irr(n)) => NodeParseResult::Complete { obj: Obj::new(n) },
+            Token::Complex(..) => {
+                let msg = "complex numbers aren't supported yet".to_string();
+                NodeParseResult::error(ParseError::invalid(msg, lex.span()))
+            },
+            _ => NodeParseResult::error(ParseError::unexpected("number", lex)),
+        }
+    }
+
+    fn none(&mut self) -> NodeParseResult {
+        unreachable!("NumParser completes on the first Lex it sees")
+    }
+
+    fn subparser_completed(&mut self, _obj: Obj) -> NodeParseResult {
+        unreachable!("NumParser doesn't push subparsers")
+    }
+}
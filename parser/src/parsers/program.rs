@@ -2,12 +2,9 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
-use sibillexer::{Lex, Token};
+use sibillexer::Lex;
 use sibiltypes::Obj;
-use parsers::{NodeParser, NodeParseResult};
-use parsers::bool::BoolParser;
-use parsers::list::ListParser;
-use parsers::sym::SymParser;
+use parsers::{datum_parser, not_a_datum, NodeParser, NodeParseResult};
 
 #[derive(Debug)]
 pub struct ProgramParser;
@@ -20,32 +17,15 @@ impl ProgramParser {
 
 impl NodeParser for ProgramParser {
     fn parse(&mut self, lex: &Lex) -> NodeParseResult {
-        match lex.token() {
-            Token::Bool(_) => {
-                let next = Box::new(BoolParser{});
-                NodeParseResult::Push { next }
-            },
-            Token::LeftParen => {
-                let next = Box::new(ListParser::new());
-                NodeParseResult::Push { next }
-            },
-            Token::RightParen => {
-                let msg = format!("Expected symbol found {:?}", lex);
-                NodeParseResult::error(msg)
-            },
-            Token::Id => {
-                let parser = SymParser{};
-                let parser = Box::new(parser);
-                NodeParseResult::Push { next: parser }
-            },
-            _ => {
-                panic!("unhandled symbol");
-            },
+        match datum_parser(lex) {
+            Some(next) => NodeParseResult::Push { next },
+            None => NodeParseResult::error(not_a_datum(lex)),
         }
     }
 
     fn none(&mut self) -> NodeParseResult {
-        NodeParseResult::Complete { obj: Obj::Null }
+        // All of the input was consumed without producing a datum, e.g. by a datum comment.
+        NodeParseResult::Discard
     }
 
     fn subparser_completed(&mut self, obj: Obj) -> NodeParseResult {
@@ -0,0 +1,69 @@
+/* parser/src/parsers/quote.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use sibillexer::{Lex, Span, Token};
+use sibiltypes::{Obj, Pair, Sym};
+use error::ParseError;
+use parsers::{datum_parser, not_a_datum, NodeParser, NodeParseResult};
+
+/// Parses one of the quote prefixes `'`, `` ` ``, `,`, or `,@` and the datum following it. The
+/// result is the datum wrapped in a list headed by the long form of the prefix, e.g. `'x` becomes
+/// `(quote x)`.
+#[derive(Debug)]
+pub struct QuoteParser {
+    name: Option<&'static str>,
+    /// The span of the prefix, once it's been seen.
+    prefix: Option<Span>,
+}
+
+impl QuoteParser {
+    pub fn new() -> QuoteParser {
+        QuoteParser { name: None, prefix: None }
+    }
+
+    fn name_for(token: &Token) -> Option<&'static str> {
+        match *token {
+            Token::Quote => Some("quote"),
+            Token::Quasiquote => Some("quasiquote"),
+            Token::Unquote => Some("unquote"),
+            Token::UnquoteSplicing => Some("unquote-splicing"),
+            _ => None,
+        }
+    }
+}
+
+impl NodeParser for QuoteParser {
+    fn parse(&mut self, lex: &Lex) -> NodeParseResult {
+        match self.name {
+            None => match QuoteParser::name_for(lex.token()) {
+                Some(name) => {
+                    self.name = Some(name);
+                    self.prefix = Some(lex.span().clone());
+                    NodeParseResult::Continue
+                },
+                None => NodeParseResult::error(ParseError::unexpected("quote", lex)),
+            },
+            Some(_) => match datum_parser(lex) {
+                Some(next) => NodeParseResult::Push { next },
+                None => NodeParseResult::error(not_a_datum(lex)),
+            },
+        }
+    }
+
+    fn none(&mut self) -> NodeParseResult {
+        let after = self.prefix.clone().expect("QuoteParser starts with a Lex");
+        NodeParseResult::error(ParseError::UnexpectedEof { expected: "datum", after })
+    }
+
+    fn subparser_completed(&mut self, obj: Obj) -> NodeParseResult {
+        match self.name {
+            Some(name) => {
+                let rest = Obj::new(Pair::with_car(obj));
+                let list = Obj::new(Pair::new(Obj::new(Sym::with_str(name)), rest));
+                NodeParseResult::Complete { obj: list }
+            },
+            None => unreachable!("QuoteParser pushes subparsers after its prefix"),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+/* parser/src/parsers/string.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use sibillexer::{Lex, Token};
+use sibiltypes::{Obj, Str};
+use error::ParseError;
+use parsers::{NodeParser, NodeParseResult};
+
+#[derive(Debug)] pub struct StrParser;
+
+impl NodeParser for StrParser {
+    fn parse(&mut self, lex: &Lex) -> NodeParseResult {
+        match *lex.token() {
+            Token::Str(ref value) => NodeParseResult::Complete { obj: Obj::new(Str::with_str(value)) },
+            _ => NodeParseResult::error(ParseError::unexpected("string", lex)),
+        }
+    }
+
+    fn none(&mut self) -> NodeParseResult {
+        unreachable!("StrParser completes on the first Lex it sees")
+    }
+
+    fn subparser_completed(&mut self, _obj: Obj) -> NodeParseResult {
+        unreachable!("StrParser doesn't push subparsers")
+    }
+}
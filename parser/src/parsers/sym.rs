@@ -4,6 +4,7 @@
 
 use sibillexer::{Lex, Token};
 use sibiltypes::{Obj, Sym};
+use error::ParseError;
 use parsers::{NodeParser, NodeParseResult};
 
 #[derive(Debug)] pub struct SymParser;
@@ -11,26 +12,20 @@ use parsers::{NodeParser, NodeParseResult};
 impl NodeParser for SymParser {
     fn parse(&mut self, lex: &Lex) -> NodeParseResult {
         match lex.token() {
-            Token::Id => {
-                let value = String::from(lex.value());
+            Token::Id(name) => {
                 // Initializing with Sym(value) caused E0423. So use this isntead.
-                let obj = Obj::new(Sym::new(value));
+                let obj = Obj::new(Sym::with_str(name));
                 NodeParseResult::Complete { obj: obj }
             }
-            _ => {
-                let msg = format!("Expected symbol, found {:?}", lex);
-                NodeParseResult::error(msg)
-            }
+            _ => NodeParseResult::error(ParseError::unexpected("symbol", lex)),
         }
     }
 
     fn none(&mut self) -> NodeParseResult {
-        let msg = format!("Expected symbol, found EOF");
-        NodeParseResult::error(msg)
+        unreachable!("SymParser completes on the first Lex it sees")
     }
 
-    fn subparser_completed(&mut self, obj: Obj) -> NodeParseResult {
-        let msg = format!("Unexpected subparser result: {}", obj);
-        NodeParseResult::error(msg)
+    fn subparser_completed(&mut self, _obj: Obj) -> NodeParseResult {
+        unreachable!("SymParser doesn't push subparsers")
     }
 }
@@ -0,0 +1,98 @@
+/* parser/src/parsers/vector.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use sibillexer::{Lex, Num, Span, Token};
+use sibiltypes::{ByteVector, Int, Obj, Vector};
+use error::ParseError;
+use parsers::{datum_parser, not_a_datum, NodeParser, NodeParseResult};
+use parsers::comment::DatumCommentParser;
+
+/// Parses a `#(...)` vector or a `#u8(...)` bytevector. Vectors can contain any datum.
+/// Bytevectors can only contain exact integers between 0 and 255, so those are read straight
+/// from the number tokens.
+#[derive(Debug)]
+pub struct VectorParser {
+    kind: Option<Kind>,
+    /// The span of the opening `#(` or `#u8(`, once it's been seen.
+    open: Option<Span>,
+    items: Vec<Obj>,
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Kind { Vector, Bytes }
+
+impl VectorParser {
+    pub fn new() -> VectorParser {
+        VectorParser { kind: None, open: None, items: Vec::new(), bytes: Vec::new() }
+    }
+
+    fn assemble(&mut self) -> Obj {
+        match self.kind {
+            Some(Kind::Bytes) => Obj::new(ByteVector::new(self.bytes.split_off(0))),
+            _ => Obj::new(Vector::new(self.items.split_off(0))),
+        }
+    }
+
+    fn parse_byte(&mut self, lex: &Lex) -> NodeParseResult {
+        match lex.token() {
+            Token::Num(Num::Int(Int(i))) if *i >= 0 && *i <= 255 => {
+                self.bytes.push(*i as u8);
+                NodeParseResult::Continue
+            },
+            Token::Num(Num::Int(_)) => {
+                let msg = format!("byte out of range: {}", lex.value());
+                NodeParseResult::error(ParseError::invalid(msg, lex.span()))
+            },
+            Token::Num(_) => {
+                let msg = format!("bytevector elements must be exact integers from 0 to 255, found {}", lex.value());
+                NodeParseResult::error(ParseError::invalid(msg, lex.span()))
+            },
+            Token::DatumComment => {
+                let next = Box::new(DatumCommentParser::new());
+                NodeParseResult::Push { next }
+            },
+            _ => NodeParseResult::error(ParseError::unexpected("byte", lex)),
+        }
+    }
+}
+
+impl NodeParser for VectorParser {
+    fn parse(&mut self, lex: &Lex) -> NodeParseResult {
+        match (&self.kind, lex.token()) {
+            (&None, Token::VectorStart) => {
+                self.kind = Some(Kind::Vector);
+                self.open = Some(lex.span().clone());
+                NodeParseResult::Continue
+            },
+            (&None, Token::ByteVectorStart) => {
+                self.kind = Some(Kind::Bytes);
+                self.open = Some(lex.span().clone());
+                NodeParseResult::Continue
+            },
+            (&None, _) => NodeParseResult::error(ParseError::unexpected("vector", lex)),
+            (&Some(_), Token::RightParen) => NodeParseResult::Complete { obj: self.assemble() },
+            (&Some(Kind::Bytes), _) => self.parse_byte(lex),
+            (&Some(Kind::Vector), _) => match datum_parser(lex) {
+                Some(next) => NodeParseResult::Push { next },
+                None => NodeParseResult::error(not_a_datum(lex)),
+            },
+        }
+    }
+
+    fn none(&mut self) -> NodeParseResult {
+        let open = self.open.clone().expect("VectorParser starts with a Lex");
+        NodeParseResult::error(ParseError::Unclosed { open })
+    }
+
+    fn subparser_completed(&mut self, obj: Obj) -> NodeParseResult {
+        match self.kind {
+            Some(Kind::Vector) => {
+                self.items.push(obj);
+                NodeParseResult::Continue
+            },
+            _ => unreachable!("VectorParser only pushes subparsers for vectors, got {}", obj),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+/* parser/src/spans.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # Spans
+//!
+//! A `Parser` remembers where in the input each datum it produces came from, so the datum can be
+//! mapped back to its source for error messages and tools. Objects are looked up by identity,
+//! which means only objects made by the parser have spans. The empty list has no identity, so it
+//! never has a span; `Parser::last_span` covers a top-level `()`.
+
+use std::collections::HashMap;
+use sibillexer::Span;
+use sibiltypes::Obj;
+
+/// A side table from parsed objects to their spans.
+#[derive(Debug, Default)]
+pub struct Spans {
+    spans: HashMap<usize, Span>,
+}
+
+impl Spans {
+    pub fn new() -> Spans {
+        Spans { spans: HashMap::new() }
+    }
+
+    /// The span of the input `obj` was parsed from, if it was made by this table's parser.
+    pub fn get(&self, obj: &Obj) -> Option<&Span> {
+        key(obj).and_then(|k| self.spans.get(&k))
+    }
+
+    pub(crate) fn insert(&mut self, obj: &Obj, span: Span) {
+        if let Some(k) = key(obj) {
+            self.spans.insert(k, span);
+        }
+    }
+}
+
+/// The identity of an object: its address, which doesn't change as references to the object are
+/// moved into lists and vectors.
+pub(crate) fn key(obj: &Obj) -> Option<usize> {
+    match *obj {
+        Obj::Null => None,
+        Obj::Ptr(ref obj) => Some(&**obj as *const _ as *const () as usize),
+    }
+}
@@ -0,0 +1,39 @@
+/* parser/src/trace.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # Tracing
+//!
+//! A `Parser` can report what it's doing to a `Trace` sink, which is handy for debugging and for
+//! asserting on the parser stack in tests. Tracing is off unless a sink is set with
+//! `Parser::set_trace`. Any `FnMut(&Event)` closure can be used as a sink.
+
+use sibillexer::Result as LexerResult;
+use sibiltypes::Obj;
+
+/// Something the parser did. Node parsers are identified by their type names, e.g.
+/// `"ListParser"`.
+#[derive(Debug)]
+pub enum Event<'a> {
+    /// The parser read a result from the lexer.
+    Read(&'a LexerResult),
+    /// The parser reached the end of its input.
+    End,
+    /// A node parser was pushed onto the stack, which is now `depth` parsers deep.
+    Push { parser: &'static str, depth: usize },
+    /// A node parser was popped off the stack, which is now `depth` parsers deep.
+    Pop { parser: &'static str, depth: usize },
+    /// The node parser on top of the stack completed with an object.
+    Complete { parser: &'static str, obj: &'a Obj },
+}
+
+/// A sink for parser `Event`s.
+pub trait Trace {
+    fn trace(&mut self, event: &Event);
+}
+
+impl<F> Trace for F where F: FnMut(&Event) {
+    fn trace(&mut self, event: &Event) {
+        self(event)
+    }
+}
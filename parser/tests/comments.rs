@@ -0,0 +1,85 @@
+/* parser/tests/comments.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests that datum comments skip exactly one datum.
+
+extern crate sibillexer;
+extern crate sibilparser;
+extern crate sibiltypes;
+
+use sibillexer::{Lex, Pos, Token};
+use sibilparser::Parser;
+use sibiltypes::{Obj, Pair, Sym};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+#[test]
+fn datum_comment_skips_top_level_datum() {
+    let tokens = vec![Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), Some(Ok(Obj::new(Sym::with_str("cd")))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn datum_comment_skips_whole_list() {
+    let tokens = vec![Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), Some(Ok(Obj::new(Sym::with_str("cd")))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn datum_comment_inside_list() {
+    let tokens = vec![Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ef"), "ef", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    let ex_list = Obj::new(Pair::new(Obj::new(Sym::with_str("ab")),
+                                     Obj::new(Pair::with_car(Obj::new(Sym::with_str("ef"))))));
+    assert_eq!(parser.next(), Some(Ok(ex_list)));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn nested_datum_comments_skip_two_datums() {
+    let tokens = vec![Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ef"), "ef", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), Some(Ok(Obj::new(Sym::with_str("ef")))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn datum_comment_at_end_of_input_produces_nothing() {
+    let tokens = vec![Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn datum_comment_without_datum_is_an_error() {
+    let tokens = vec![Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert!(parser.next().unwrap().is_err());
+}
@@ -0,0 +1,75 @@
+/* parser/tests/complete.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for telling whole input from input that needs more, the way a REPL does.
+
+extern crate sibilparser;
+extern crate sibiltypes;
+
+use sibilparser::complete::{check, Completeness};
+use sibiltypes::{Obj, Sym};
+
+fn sym(name: &str) -> Obj {
+    Obj::new(Sym::with_str(name))
+}
+
+fn incomplete(input: &str) -> usize {
+    match check(input) {
+        Completeness::Incomplete { depth, .. } => depth,
+        other => panic!("{:?} should be incomplete, found {:?}", input, other),
+    }
+}
+
+#[test]
+fn whole_datums_are_complete() {
+    assert_eq!(check("a b"), Completeness::Complete(vec![sym("a"), sym("b")]));
+    match check("(define (f x) x)\n") {
+        Completeness::Complete(ref datums) => assert_eq!(datums[0].to_string(), "(define (f x) x)"),
+        other => panic!("expected a complete datum, found {:?}", other),
+    }
+}
+
+#[test]
+fn empty_input_is_complete() {
+    assert_eq!(check(""), Completeness::Complete(vec![]));
+    assert_eq!(check("  ; a comment\n#| and another |#"), Completeness::Complete(vec![]));
+}
+
+#[test]
+fn unclosed_lists_are_incomplete() {
+    assert_eq!(incomplete("(define (f x)"), 1);
+    assert_eq!(incomplete("(define (f x\n"), 2);
+    assert_eq!(incomplete("#(1 #u8(2"), 2);
+    assert_eq!(incomplete("(a . "), 1);
+}
+
+#[test]
+fn unfinished_prefixes_are_incomplete() {
+    assert_eq!(incomplete("'"), 0);
+    assert_eq!(incomplete("(a #;"), 1);
+    assert_eq!(incomplete("#0="), 0);
+}
+
+#[test]
+fn unfinished_tokens_are_incomplete() {
+    assert_eq!(incomplete("(display \"hello"), 1);
+    assert_eq!(incomplete("|a b"), 0);
+    assert_eq!(incomplete("#| a comment"), 0);
+}
+
+#[test]
+fn datums_before_the_unfinished_one_are_kept() {
+    let expected = Completeness::Incomplete { datums: vec![sym("a"), sym("b")], depth: 1 };
+    assert_eq!(check("a b (c"), expected);
+}
+
+#[test]
+fn errors_are_not_incomplete() {
+    for input in &["a )", "(a . b c", "(. a", "#u8(256"] {
+        match check(input) {
+            Completeness::Error(_) => {},
+            other => panic!("{:?} should be an error, found {:?}", input, other),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+/* parser/tests/errors.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for parse errors, and for picking up again at the next top-level datum after one.
+
+extern crate sibillexer;
+extern crate sibilparser;
+extern crate sibiltypes;
+
+use std::error::Error;
+use sibillexer::{ErrorKind, Lexer, Pos, Span};
+use sibilparser::{ParseError, Parser};
+use sibiltypes::{Obj, Sym};
+
+fn parse(input: &str) -> Vec<Result<Obj, ParseError>> {
+    Parser::new(Lexer::new(input.chars())).collect()
+}
+
+fn span(line: usize, column: usize, offset: usize, len: usize) -> Span {
+    let start = Pos::new(line, column, offset);
+    Span::new(start, start.after_bytes(len))
+}
+
+fn sym(name: &str) -> Result<Obj, ParseError> {
+    Ok(Obj::new(Sym::with_str(name)))
+}
+
+#[test]
+fn unclosed_list_points_at_the_open_paren() {
+    assert_eq!(parse("a (b (c)"), vec![sym("a"), Err(ParseError::Unclosed { open: span(1, 3, 2, 1) })]);
+}
+
+#[test]
+fn unopened_close_paren() {
+    assert_eq!(parse("a ) b"), vec![sym("a"), Err(ParseError::Unopened { close: span(1, 3, 2, 1) }), sym("b")]);
+}
+
+#[test]
+fn misplaced_dots() {
+    assert_eq!(parse(". a"), vec![Err(ParseError::MisplacedDot { dot: span(1, 1, 0, 1) }), sym("a")]);
+    assert_eq!(parse("(. a) b"), vec![Err(ParseError::MisplacedDot { dot: span(1, 2, 1, 1) }), sym("b")]);
+}
+
+#[test]
+fn quote_at_end_of_input() {
+    let error = ParseError::UnexpectedEof { expected: "datum", after: span(1, 3, 2, 1) };
+    assert_eq!(parse("a '"), vec![sym("a"), Err(error)]);
+}
+
+#[test]
+fn lexer_errors_are_wrapped() {
+    let parsed = parse("#u9 a");
+    let error = parsed[0].as_ref().unwrap_err();
+    match *error {
+        ParseError::Lexer(ref err) => assert_eq!(err.kind(), ErrorKind::InvalidChar),
+        ref other => panic!("expected a lexer error, found {:?}", other),
+    }
+    assert!(error.source().is_some());
+    assert_eq!(error.to_string(), "1:3: invalid character: 9");
+}
+
+#[test]
+fn lexer_errors_have_an_empty_span_at_their_position() {
+    let parsed = parse("#u9 a");
+    let error = parsed[0].as_ref().unwrap_err();
+    assert_eq!(error.span(), span(1, 3, 2, 0));
+
+    let parsed: Vec<_> = Parser::new(Lexer::with_file("(a #q)".chars(), "bad.scm")).collect();
+    let error = parsed[0].as_ref().unwrap_err();
+    assert_eq!(error.span(), span(1, 5, 4, 0).in_file(Some("bad.scm".into())));
+    assert_eq!(error.span().to_string(), "bad.scm:1:5");
+}
+
+#[test]
+fn parse_errors_span_the_datum() {
+    let parsed = parse("a (b (c)");
+    assert_eq!(parsed[1].as_ref().unwrap_err().span(), span(1, 3, 2, 1));
+}
+
+#[test]
+fn errors_say_where_they_are() {
+    let messages: Vec<String> = parse("(a\n  #u8(1 bc))").into_iter()
+        .filter_map(|r| r.err())
+        .map(|e| e.to_string())
+        .collect();
+    assert_eq!(messages, vec!["2:9: expected byte, found bc"]);
+}
+
+#[test]
+fn every_mistake_is_reported() {
+    let parsed = parse("(a #1#) b\n#u8(300 1) (c (d)) )\n(e");
+    let messages: Vec<String> = parsed.iter().map(|r| match *r {
+        Ok(ref obj) => obj.to_string(),
+        Err(ref err) => err.to_string(),
+    }).collect();
+    assert_eq!(messages, vec![
+        "1:4: undefined datum label: #1#",
+        "b",
+        "2:5: byte out of range: 300",
+        "(c (d))",
+        "2:20: unexpected closing paren",
+        "3:1: unclosed paren",
+    ]);
+}
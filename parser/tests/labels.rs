@@ -0,0 +1,146 @@
+/* parser/tests/labels.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for datum labels, and the shared and cyclic structures they make.
+
+extern crate sibillexer;
+extern crate sibilparser;
+extern crate sibiltypes;
+
+use sibillexer::{Lexer, Pos, Span};
+use sibilparser::{ParseError, Parser};
+use sibiltypes::{Obj, Pair, Sym};
+
+fn parse(input: &str) -> Vec<Result<Obj, ParseError>> {
+    Parser::new(Lexer::new(input.chars())).collect()
+}
+
+fn parse_one(input: &str) -> Obj {
+    let mut parsed = parse(input);
+    assert_eq!(parsed.len(), 1, "{} should be one datum", input);
+    parsed.remove(0).unwrap()
+}
+
+fn car(obj: &Obj) -> Obj {
+    obj.obj().and_then(|o| o.as_pair()).expect("expected a pair").car()
+}
+
+fn cdr(obj: &Obj) -> Obj {
+    obj.obj().and_then(|o| o.as_pair()).expect("expected a pair").cdr()
+}
+
+fn errors(input: &str) -> Vec<String> {
+    parse(input).into_iter().filter_map(|r| r.err()).map(|e| e.to_string()).collect()
+}
+
+#[test]
+fn labeled_datum_without_references() {
+    let obj = parse_one("#0=(a b)");
+    let ex = Pair::new(Obj::new(Sym::with_str("a")), Obj::new(Pair::with_car(Obj::new(Sym::with_str("b")))));
+    assert_eq!(obj, Obj::new(ex));
+}
+
+#[test]
+fn references_share_structure() {
+    let obj = parse_one("(#0=(x) #0# #0#)");
+    let first = car(&obj);
+    assert!(first.ptr_eq(&car(&cdr(&obj))));
+    assert!(first.ptr_eq(&car(&cdr(&cdr(&obj)))));
+    assert_eq!(obj.to_string(), "((x) (x) (x))");
+}
+
+#[test]
+fn cyclic_list() {
+    let obj = parse_one("#0=(a b . #0#)");
+    assert!(cdr(&cdr(&obj)).ptr_eq(&obj));
+    assert_eq!(obj.to_string(), "#0=(a b . #0#)");
+}
+
+#[test]
+fn list_containing_itself() {
+    let obj = parse_one("#0=(a #0#)");
+    assert!(car(&cdr(&obj)).ptr_eq(&obj));
+    assert_eq!(obj.to_string(), "#0=(a #0#)");
+}
+
+#[test]
+fn cyclic_vector() {
+    let obj = parse_one("#1=#(a #1# (#1#))");
+    let vector = obj.obj().and_then(|o| o.as_vector()).unwrap();
+    assert!(vector.get(1).unwrap().ptr_eq(&obj));
+    assert!(car(&vector.get(2).unwrap()).ptr_eq(&obj));
+    assert_eq!(obj.to_string(), "#0=#(a #0# (#0#))");
+}
+
+#[test]
+fn nested_labels() {
+    let obj = parse_one("#0=(#1=(#0#) . #1#)");
+    let inner = car(&obj);
+    assert!(cdr(&obj).ptr_eq(&inner));
+    assert!(car(&inner).ptr_eq(&obj));
+}
+
+#[test]
+fn several_labels_on_one_datum() {
+    let obj = parse_one("(#0=#1=x #0# #1#)");
+    let x = car(&obj);
+    assert!(car(&cdr(&obj)).ptr_eq(&x));
+    assert!(car(&cdr(&cdr(&obj))).ptr_eq(&x));
+}
+
+#[test]
+fn quoted_references() {
+    let obj = parse_one("#0='#0#");
+    assert!(car(&cdr(&obj)).ptr_eq(&obj));
+    assert_eq!(obj.to_string(), "#0=(quote #0#)");
+}
+
+#[test]
+fn cycles_are_equal_to_similar_cycles() {
+    assert_eq!(parse_one("#0=(a . #0#)"), parse_one("#5=(a a . #5#)"));
+    assert_ne!(parse_one("#0=(a . #0#)"), parse_one("#0=(b . #0#)"));
+}
+
+#[test]
+fn references_keep_the_span_of_the_labeled_datum() {
+    let mut parser = Parser::new(Lexer::new("(#0=abc #0#)".chars()));
+    let obj = parser.next().unwrap().unwrap();
+    let span = Span::new(Pos::new(1, 5, 4), Pos::new(1, 8, 7));
+    assert_eq!(parser.spans().get(&car(&obj)), Some(&span));
+}
+
+#[test]
+fn labels_are_scoped_to_one_top_level_datum() {
+    let parsed = parse("#0=a #0#");
+    assert_eq!(parsed[0], Ok(Obj::new(Sym::with_str("a"))));
+    assert_eq!(parsed[1].as_ref().unwrap_err().to_string(), "1:6: undefined datum label: #0#");
+}
+
+#[test]
+fn labels_in_discarded_data_are_scoped_to_them() {
+    assert_eq!(errors("#;#0=(a) #0#"), vec!["1:10: undefined datum label: #0#"]);
+    assert_eq!(errors("#;#0=(a) (b #0#)"), vec!["1:13: undefined datum label: #0#"]);
+    assert_eq!(parse_one("(#;#0=a #0#)").to_string(), "(a)");
+}
+
+#[test]
+fn undefined_labels() {
+    assert_eq!(errors("(a #1#)"), vec!["1:4: undefined datum label: #1#"]);
+}
+
+#[test]
+fn labels_defined_twice() {
+    assert_eq!(errors("(#0=a #0=b)"), vec!["1:7: datum label defined twice: #0="]);
+}
+
+#[test]
+fn labels_referring_only_to_themselves() {
+    assert_eq!(errors("#0=#0#"), vec!["1:4: datum label #0= refers only to itself"]);
+}
+
+#[test]
+fn labels_need_a_datum() {
+    assert!(parse("(a #0=)")[0].is_err());
+    assert!(parse("#0=")[0].is_err());
+}
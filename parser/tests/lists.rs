@@ -8,18 +8,22 @@ extern crate sibillexer;
 extern crate sibilparser;
 extern crate sibiltypes;
 
-use sibillexer::{Lex, Token};
-use sibilparser::Parser;
-use sibiltypes::{Obj, Pair, Sym};
+use sibillexer::{Lex, Lexer, Pos, Span, Token};
+use sibilparser::{ParseError, Parser};
+use sibiltypes::{Int, Obj, Pair, Sym};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
 
 #[test]
 fn list_of_four_tokens() {
-    let tokens = vec![Ok(Lex::new(Token::LeftParen, "(", 0, 0)),
-                      Ok(Lex::new(Token::Id, "ab", 0, 0)),
-                      Ok(Lex::new(Token::Id, "cd", 0, 0)),
-                      Ok(Lex::new(Token::Id, "ef", 0, 0)),
-                      Ok(Lex::new(Token::Id, "gh", 0, 0)),
-                      Ok(Lex::new(Token::RightParen, ")", 0, 0))].into_iter();
+    let tokens = vec![Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ef"), "ef", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("gh"), "gh", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0)))].into_iter();
     let mut parser = Parser::new(tokens);
 
     let ex_list = Obj::new(
@@ -34,11 +38,11 @@ fn list_of_four_tokens() {
 
 #[test]
 fn single_dotted_pair() {
-    let tokens = vec![Ok(Lex::new(Token::LeftParen, "(", 0, 0)),
-                      Ok(Lex::new(Token::Id, "ab", 0, 0)),
-                      Ok(Lex::new(Token::Dot, ".", 0, 0)),
-                      Ok(Lex::new(Token::Id, "cd", 0, 0)),
-                      Ok(Lex::new(Token::RightParen, ")", 0, 0))].into_iter();
+    let tokens = vec![Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::Dot, ".", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0)))].into_iter();
     let mut parser = Parser::new(tokens);
     let ex_list = Obj::new(Pair::new(Obj::new(Sym::with_str("ab")), Obj::new(Sym::with_str("cd"))));
     assert_eq!(parser.next(), Some(Ok(ex_list)));
@@ -47,15 +51,86 @@ fn single_dotted_pair() {
 
 #[test]
 fn three_element_dotted_pair() {
-    let tokens = vec![Ok(Lex::new(Token::LeftParen, "(", 0, 0)),
-                      Ok(Lex::new(Token::Id, "ab", 0, 0)),
-                      Ok(Lex::new(Token::Id, "cd", 0, 0)),
-                      Ok(Lex::new(Token::Dot, ".", 0, 0)),
-                      Ok(Lex::new(Token::Id, "ef", 0, 0)),
-                      Ok(Lex::new(Token::RightParen, ")", 0, 0))].into_iter();
+    let tokens = vec![Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::Dot, ".", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ef"), "ef", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0)))].into_iter();
     let mut parser = Parser::new(tokens);
     let ex_list = Obj::new(Pair::new(Obj::new(Sym::with_str("ab")), Obj::new(
                 Pair::new(Obj::new(Sym::with_str("cd")), Obj::new(Sym::with_str("ef"))))));
     assert_eq!(parser.next(), Some(Ok(ex_list)));
     assert_eq!(parser.next(), None);
 }
+
+#[test]
+fn list_of_numbers() {
+    let mut parser = Parser::new(Lexer::new("(+ 1 2)".chars()));
+    let ex_list = Obj::new(Pair::new(Obj::new(Sym::with_str("+")), Obj::new(
+        Pair::new(Obj::new(Int(1)), Obj::new(Pair::with_car(Obj::new(Int(2))))))));
+    assert_eq!(parser.next(), Some(Ok(ex_list)));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn list_with_quoted_items() {
+    let mut parser = Parser::new(Lexer::new("('a `(b ,c))".chars()));
+    let obj = parser.next().unwrap().unwrap();
+    assert_eq!(obj.to_string(), "((quote a) (quasiquote (b (unquote c))))");
+    assert_eq!(parser.next(), None);
+}
+
+fn parse(input: &str) -> Vec<String> {
+    Parser::new(Lexer::new(input.chars())).map(|r| match r {
+        Ok(obj) => obj.to_string(),
+        Err(err) => err.to_string(),
+    }).collect()
+}
+
+#[test]
+fn well_formed_dotted_lists() {
+    assert_eq!(parse("(a . b)"), vec!["(a . b)"]);
+    assert_eq!(parse("(a b . c)"), vec!["(a b . c)"]);
+    assert_eq!(parse("(a . (b c))"), vec!["(a b c)"]);
+    assert_eq!(parse("(a . ())"), vec!["(a)"]);
+    assert_eq!(parse("(a . #;b c)"), vec!["(a . c)"]);
+    assert_eq!(parse("(a . b #;c)"), vec!["(a . b)"]);
+    assert_eq!(parse("(a . 'b)"), vec!["(a quote b)"]);
+}
+
+#[test]
+fn malformed_dotted_lists() {
+    let cases = [
+        ("(. a)", "1:2: misplaced dot"),
+        ("(.)", "1:2: misplaced dot"),
+        ("(a . )", "1:6: expected datum after dot, found )"),
+        ("(a . #;b)", "1:9: expected datum after dot, found )"),
+        ("(a . b c)", "1:8: expected closing paren, found c"),
+        ("(a . b (c))", "1:8: expected closing paren, found ("),
+        ("(a . . b)", "1:6: misplaced dot"),
+        ("(a . b . c)", "1:8: misplaced dot"),
+        ("(a b . c . d)", "1:10: misplaced dot"),
+        ("(a . b", "1:1: unclosed paren"),
+        ("(a .", "1:1: unclosed paren"),
+        ("#(a . b)", "1:5: misplaced dot"),
+        ("'. a", "1:2: misplaced dot"),
+    ];
+    for &(input, error) in cases.iter() {
+        assert_eq!(parse(input)[0], error, "parsing {}", input);
+    }
+}
+
+#[test]
+fn malformed_dotted_lists_are_skipped() {
+    assert_eq!(parse("(a . b c) d (. e) f"), vec!["1:8: expected closing paren, found c", "d",
+                                                  "1:14: misplaced dot", "f"]);
+}
+
+#[test]
+fn misplaced_dot_points_at_the_dot() {
+    let mut parser = Parser::new(Lexer::new("(a . b . c)".chars()));
+    let dot = Span::new(Pos::new(1, 8, 7), Pos::new(1, 9, 8));
+    assert_eq!(parser.next(), Some(Err(ParseError::MisplacedDot { dot })));
+    assert_eq!(parser.next(), None);
+}
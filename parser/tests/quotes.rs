@@ -0,0 +1,85 @@
+/* parser/tests/quotes.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests that quote prefixes are expanded into lists.
+
+extern crate sibillexer;
+extern crate sibilparser;
+extern crate sibiltypes;
+
+use sibillexer::{Lex, Pos, Token};
+use sibilparser::Parser;
+use sibiltypes::{Obj, Pair, Sym};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+fn sym(name: &str) -> Obj {
+    Obj::new(Sym::with_str(name))
+}
+
+fn list2(a: Obj, b: Obj) -> Obj {
+    Obj::new(Pair::new(a, Obj::new(Pair::with_car(b))))
+}
+
+#[test]
+fn prefixes_expand_to_lists() {
+    let prefixes = vec![(Token::Quote, "'", "quote"),
+                        (Token::Quasiquote, "`", "quasiquote"),
+                        (Token::Unquote, ",", "unquote"),
+                        (Token::UnquoteSplicing, ",@", "unquote-splicing")];
+    for (token, value, name) in prefixes {
+        let tokens = vec![Ok(Lex::at(token, value, Pos::new(1, 1, 0))),
+                          Ok(Lex::at(id("abc"), "abc", Pos::new(1, 1, 0)))].into_iter();
+        let mut parser = Parser::new(tokens);
+        assert_eq!(parser.next(), Some(Ok(list2(sym(name), sym("abc")))));
+        assert_eq!(parser.next(), None);
+    }
+}
+
+#[test]
+fn quoted_list() {
+    let tokens = vec![Ok(Lex::at(Token::Quote, "'", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), Some(Ok(list2(sym("quote"), list2(sym("ab"), sym("cd"))))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn quotes_inside_lists_and_nested() {
+    let tokens = vec![Ok(Lex::at(Token::Quasiquote, "`", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::Unquote, ",", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::Quote, "'", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    let unquoted = list2(sym("unquote"), list2(sym("quote"), sym("ab")));
+    let ex = list2(sym("quasiquote"), Obj::new(Pair::with_car(unquoted)));
+    assert_eq!(parser.next(), Some(Ok(ex)));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn quote_skips_datum_comment() {
+    let tokens = vec![Ok(Lex::at(Token::Quote, "'", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::DatumComment, "#;", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), Some(Ok(list2(sym("quote"), sym("cd")))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn quote_without_datum_is_an_error() {
+    let tokens = vec![Ok(Lex::at(Token::Quote, "'", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert!(parser.next().unwrap().is_err());
+}
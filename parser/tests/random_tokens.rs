@@ -0,0 +1,79 @@
+/* parser/tests/random_tokens.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! A property test: no stream of tokens, however nonsensical, makes the parser panic or read
+//! forever.
+
+extern crate sibillexer;
+extern crate sibilparser;
+extern crate sibiltypes;
+
+use sibillexer::{Error, Lex, Num, Pos, Token};
+use sibillexer::Result as LexerResult;
+use sibilparser::Parser;
+use sibiltypes::{Frac, Int, Irr};
+
+/// A xorshift generator, so the streams are random but the same on every run.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+/// Every token there is, and a lexer error.
+fn random_lex(rng: &mut Rng, pos: Pos) -> LexerResult {
+    let (token, value) = match rng.below(23) {
+        0 => (Token::Bool(true), "#t"),
+        1 => (Token::Char('a'), "#\\a"),
+        2 => (Token::Complex(Num::Int(Int(1)), Num::Int(Int(2))), "1+2i"),
+        3 => (Token::ByteVectorStart, "#u8("),
+        4 => (Token::DatumComment, "#;"),
+        5 => (Token::DatumLabel(rng.below(3) as usize), "#0="),
+        6 => (Token::DatumRef(rng.below(3) as usize), "#0#"),
+        7 => (Token::Dot, "."),
+        8 => (Token::Eof, ""),
+        9 => (Token::Id("a".to_string()), "a"),
+        10 | 11 => (Token::LeftParen, "("),
+        12 => (Token::Num(Num::Int(Int(rng.below(300) as i64))), "1"),
+        13 => (Token::Num(Num::Frac(Frac::new(Int(1), Int(2)).unwrap())), "1/2"),
+        14 => (Token::Num(Num::Irr(Irr(1.5))), "1.5"),
+        15 => (Token::Quasiquote, "`"),
+        16 => (Token::Quote, "'"),
+        17 | 18 => (Token::RightParen, ")"),
+        19 => (Token::Str("a".to_string()), "\"a\""),
+        20 => (Token::Unquote, ","),
+        21 => (Token::UnquoteSplicing, ",@"),
+        _ => match rng.below(2) {
+            0 => (Token::VectorStart, "#("),
+            _ => return Err(Error::invalid_char('x')),
+        },
+    };
+    Ok(Lex::at(token, value, pos))
+}
+
+#[test]
+fn random_token_streams_never_panic() {
+    let mut rng = Rng(0x5eed_1234_abcd_0001);
+    for case in 0..5000 {
+        let len = rng.below(40) as usize;
+        let tokens: Vec<LexerResult> = (0..len)
+            .map(|i| random_lex(&mut rng, Pos::new(1, i + 1, i)))
+            .collect();
+        let parser = Parser::new(tokens.into_iter());
+        let mut results = 0;
+        for _ in parser {
+            results += 1;
+            assert!(results <= len, "more results than tokens in case {}", case);
+        }
+    }
+}
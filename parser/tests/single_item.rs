@@ -8,14 +8,18 @@ extern crate sibillexer;
 extern crate sibilparser;
 extern crate sibiltypes;
 
-use sibillexer::{Lex, Token};
+use sibillexer::{Lex, Lexer, Num, Pos, Token};
 use sibillexer::Result as LexerResult;
 use sibilparser::Parser;
-use sibiltypes::{Bool, Obj, Sym};
+use sibiltypes::{Bool, Char, Frac, Int, Irr, Obj, Str, Sym};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
 
 #[test]
 fn single_sym() {
-    let lex: LexerResult = Ok(Lex::new(Token::Id, "abc", 0, 0));
+    let lex: LexerResult = Ok(Lex::at(id("abc"), "abc", Pos::new(1, 1, 0)));
     let tokens = vec![lex].into_iter();
     let mut parser = Parser::new(tokens);
     assert_eq!(parser.next(), Some(Ok(Obj::new(Sym::with_str("abc")))));
@@ -24,8 +28,8 @@ fn single_sym() {
 
 #[test]
 fn single_pair() {
-    let tokens = vec![Ok(Lex::new(Token::LeftParen, "(", 0, 0)),
-                      Ok(Lex::new(Token::RightParen, ")", 0, 0))].into_iter();
+    let tokens = vec![Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 1, 0)))].into_iter();
     let mut parser = Parser::new(tokens);
     assert_eq!(parser.next(), Some(Ok(Obj::Null)));
     assert_eq!(parser.next(), None);
@@ -33,9 +37,60 @@ fn single_pair() {
 
 #[test]
 fn single_bool() {
-    let lex: LexerResult = Ok(Lex::new(Token::Bool(true), "#t", 0, 0));
+    let lex: LexerResult = Ok(Lex::at(Token::Bool(true), "#t", Pos::new(1, 1, 0)));
     let tokens = vec![lex].into_iter();
     let mut parser = Parser::new(tokens);
     assert_eq!(parser.next(), Some(Ok(Obj::new(Bool::True))));
     assert_eq!(parser.next(), None);
 }
+
+#[test]
+fn eof_token_ends_the_input() {
+    let tokens = vec![Ok(Lex::at(id("abc"), "abc", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::Eof, "", Pos::new(1, 4, 3)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), Some(Ok(Obj::new(Sym::with_str("abc")))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn single_numbers() {
+    let half = Frac::new(Int(1), Int(2)).unwrap();
+    let tokens = vec![Ok(Lex::at(Token::Num(Num::Int(Int(42))), "42", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::Num(Num::Frac(half)), "1/2", Pos::new(1, 4, 3))),
+                      Ok(Lex::at(Token::Num(Num::Irr(Irr(2.5))), "2.5", Pos::new(1, 8, 7)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), Some(Ok(Obj::new(Int(42)))));
+    assert_eq!(parser.next(), Some(Ok(Obj::new(half))));
+    assert_eq!(parser.next(), Some(Ok(Obj::new(Irr(2.5)))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn single_char() {
+    let tokens = vec![Ok(Lex::at(Token::Char('λ'), "#\\x3bb", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    let obj = parser.next().unwrap().unwrap();
+    assert_eq!(obj, Obj::new(Char('λ')));
+    assert_eq!(obj.to_string(), "#\\x3bb");
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn single_string() {
+    let tokens = vec![Ok(Lex::at(Token::Str("a\"b".to_string()), "\"a\\\"b\"", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    let obj = parser.next().unwrap().unwrap();
+    assert_eq!(obj, Obj::new(Str::with_str("a\"b")));
+    assert_eq!(obj.to_string(), "\"a\\\"b\"");
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn strings_read_back_the_way_they_are_written() {
+    let parse = |input: &str| Parser::new(Lexer::new(input.chars())).next().unwrap().unwrap();
+    for value in &["plain", "say \"hi\"", "back\\slash", "tab\tnew\nline\r", "bell\x07\x01\x7f", "λ", ""] {
+        let written = Obj::new(Str::with_str(value)).to_string();
+        assert_eq!(parse(&written), Obj::new(Str::with_str(value)), "reading {}", written);
+    }
+}
@@ -0,0 +1,63 @@
+/* parser/tests/spans.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests that parsed datums can be mapped back to where they came from in the input.
+
+extern crate sibillexer;
+extern crate sibilparser;
+extern crate sibiltypes;
+
+use sibillexer::{Lexer, Pos, Span};
+use sibilparser::Parser;
+use sibiltypes::Obj;
+
+fn span(start: (usize, usize, usize), end: (usize, usize, usize)) -> Span {
+    Span::new(Pos::new(start.0, start.1, start.2), Pos::new(end.0, end.1, end.2))
+}
+
+#[test]
+fn nested_datums_have_spans() {
+    let mut parser = Parser::new(Lexer::new("(ab\n  #(#t cd))".chars()));
+    let list = parser.next().unwrap().unwrap();
+    assert_eq!(parser.spans().get(&list), Some(&span((1, 1, 0), (2, 12, 15))));
+
+    let pair = list.obj().unwrap().as_pair().unwrap();
+    assert_eq!(parser.spans().get(&pair.car()), Some(&span((1, 2, 1), (1, 4, 3))));
+
+    let vector = pair.cdr().obj().unwrap().as_pair().unwrap().car();
+    let items: Vec<Obj> = vector.obj().unwrap().as_vector().unwrap().iter().collect();
+    assert_eq!(parser.spans().get(&items[0]), Some(&span((2, 5, 8), (2, 7, 10))));
+    assert_eq!(parser.spans().get(&items[1]), Some(&span((2, 8, 11), (2, 10, 13))));
+}
+
+#[test]
+fn top_level_datums_have_spans() {
+    let mut parser = Parser::new(Lexer::new("abc  'de ()".chars()));
+    let abc = parser.next().unwrap().unwrap();
+    assert_eq!(parser.spans().get(&abc), Some(&span((1, 1, 0), (1, 4, 3))));
+    assert_eq!(parser.last_span(), Some(&span((1, 1, 0), (1, 4, 3))));
+
+    let quoted = parser.next().unwrap().unwrap();
+    assert_eq!(parser.spans().get(&quoted), Some(&span((1, 6, 5), (1, 9, 8))));
+
+    let empty = parser.next().unwrap().unwrap();
+    assert_eq!(parser.spans().get(&empty), None);
+    assert_eq!(parser.last_span(), Some(&span((1, 10, 9), (1, 12, 11))));
+}
+
+#[test]
+fn datum_comments_are_not_part_of_the_span() {
+    let mut parser = Parser::new(Lexer::new("#; ab cd".chars()));
+    let cd = parser.next().unwrap().unwrap();
+    assert_eq!(parser.spans().get(&cd), Some(&span((1, 7, 6), (1, 9, 8))));
+}
+
+#[test]
+fn spans_name_the_file() {
+    let mut parser = Parser::new(Lexer::with_file("\n  abc".chars(), "main.scm"));
+    let abc = parser.next().unwrap().unwrap();
+    let span = parser.spans().get(&abc).unwrap();
+    assert_eq!(span.file(), Some("main.scm"));
+    assert_eq!(span.to_string(), "main.scm:2:3");
+}
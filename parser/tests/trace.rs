@@ -0,0 +1,62 @@
+/* parser/tests/trace.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for the parser's trace hook.
+
+extern crate sibillexer;
+extern crate sibilparser;
+extern crate sibiltypes;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use sibillexer::{Lex, Pos, Token};
+use sibilparser::Parser;
+use sibilparser::trace::Event;
+
+/// Parse all of `tokens`, and collect the trace events as strings.
+fn trace(tokens: Vec<Lex>) -> Vec<String> {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let sink = events.clone();
+    let mut parser = Parser::new(tokens.into_iter().map(Ok));
+    parser.set_trace(move |event: &Event| {
+        let desc = match *event {
+            Event::Read(Ok(ref lex)) => format!("read {}", lex.value()),
+            Event::Read(Err(ref error)) => format!("read error {}", error),
+            Event::End => "end".to_string(),
+            Event::Push { parser, depth } => format!("push {} {}", parser, depth),
+            Event::Pop { parser, depth } => format!("pop {} {}", parser, depth),
+            Event::Complete { parser, obj } => format!("complete {} {}", parser, obj),
+        };
+        sink.borrow_mut().push(desc);
+    });
+    for _ in parser { }
+    let out = events.borrow().clone();
+    out
+}
+
+#[test]
+fn traces_stack_pushes_and_pops() {
+    let tokens = vec![Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0)),
+                      Lex::at(Token::Id("ab".to_string()), "ab", Pos::new(1, 2, 1)),
+                      Lex::at(Token::RightParen, ")", Pos::new(1, 4, 3))];
+    assert_eq!(trace(tokens), vec!["read (",
+                                   "push ProgramParser 1",
+                                   "push ListParser 2",
+                                   "read ab",
+                                   "push SymParser 3",
+                                   "complete SymParser ab",
+                                   "pop SymParser 2",
+                                   "read )",
+                                   "complete ListParser (ab)",
+                                   "pop ListParser 1",
+                                   "complete ProgramParser (ab)",
+                                   "pop ProgramParser 0",
+                                   "end"]);
+}
+
+#[test]
+fn tracing_is_off_by_default() {
+    let tokens = vec![Ok(Lex::at(Token::Bool(true), "#t", Pos::new(1, 1, 0)))];
+    assert_eq!(Parser::new(tokens.into_iter()).count(), 1);
+}
@@ -0,0 +1,112 @@
+/* parser/tests/vectors.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Tests for parsing vectors and bytevectors.
+
+extern crate sibillexer;
+extern crate sibilparser;
+extern crate sibiltypes;
+
+use sibillexer::{Lex, Lexer, Num, Pos, Span, Token};
+use sibilparser::{ParseError, Parser};
+use sibiltypes::{Bool, ByteVector, Int, Obj, Pair, Sym, Vector};
+
+fn id(name: &str) -> Token {
+    Token::Id(name.to_string())
+}
+
+fn byte(i: i64, start: Pos) -> Lex {
+    Lex::at(Token::Num(Num::Int(Int(i))), &i.to_string(), start)
+}
+
+#[test]
+fn empty_vector() {
+    let tokens = vec![Ok(Lex::at(Token::VectorStart, "#(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 3, 2)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), Some(Ok(Obj::new(Vector::empty()))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn vector_of_data() {
+    let tokens = vec![Ok(Lex::at(Token::VectorStart, "#(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 3, 2))),
+                      Ok(Lex::at(Token::Bool(true), "#t", Pos::new(1, 6, 5))),
+                      Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 9, 8))),
+                      Ok(Lex::at(id("cd"), "cd", Pos::new(1, 10, 9))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 12, 11))),
+                      Ok(Lex::at(Token::VectorStart, "#(", Pos::new(1, 14, 13))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 16, 15))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 17, 16)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    let ex = Vector::new(vec![Obj::new(Sym::with_str("ab")),
+                              Obj::new(Bool::True),
+                              Obj::new(Pair::with_car(Obj::new(Sym::with_str("cd")))),
+                              Obj::new(Vector::empty())]);
+    assert_eq!(parser.next(), Some(Ok(Obj::new(ex))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn vector_inside_list() {
+    let tokens = vec![Ok(Lex::at(Token::LeftParen, "(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(Token::ByteVectorStart, "#u8(", Pos::new(1, 2, 1))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 6, 5))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 7, 6)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    let ex = Pair::with_car(Obj::new(ByteVector::new(vec![])));
+    assert_eq!(parser.next(), Some(Ok(Obj::new(ex))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn bytevector() {
+    let tokens = vec![Ok(Lex::at(Token::ByteVectorStart, "#u8(", Pos::new(1, 1, 0))),
+                      Ok(byte(0, Pos::new(1, 5, 4))),
+                      Ok(byte(255, Pos::new(1, 7, 6))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 10, 9)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert_eq!(parser.next(), Some(Ok(Obj::new(ByteVector::new(vec![0, 255])))));
+    assert_eq!(parser.next(), None);
+}
+
+#[test]
+fn bytevector_rejects_out_of_range_bytes() {
+    let tokens = vec![Ok(Lex::at(Token::ByteVectorStart, "#u8(", Pos::new(1, 1, 0))),
+                      Ok(byte(1, Pos::new(1, 5, 4))),
+                      Ok(byte(256, Pos::new(2, 3, 8))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(2, 6, 11)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    let msg = "byte out of range: 256".to_string();
+    let span = Span::new(Pos::new(2, 3, 8), Pos::new(2, 6, 11));
+    assert_eq!(parser.next(), Some(Err(ParseError::Invalid { msg, span })));
+}
+
+#[test]
+fn bytevector_rejects_numbers_that_are_not_exact_integers() {
+    let parse = |input: &str| Parser::new(Lexer::new(input.chars())).next().unwrap().unwrap_err().to_string();
+    assert_eq!(parse("#u8(1.0)"), "1:5: bytevector elements must be exact integers from 0 to 255, found 1.0");
+    assert_eq!(parse("#u8(1/2)"), "1:5: bytevector elements must be exact integers from 0 to 255, found 1/2");
+    assert_eq!(parse("#u8(-1)"), "1:5: byte out of range: -1");
+}
+
+#[test]
+fn bytevector_rejects_non_numbers() {
+    let tokens = vec![Ok(Lex::at(Token::ByteVectorStart, "#u8(", Pos::new(1, 1, 0))),
+                      Ok(Lex::at(id("ab"), "ab", Pos::new(1, 5, 4))),
+                      Ok(Lex::at(Token::RightParen, ")", Pos::new(1, 7, 6)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    let error = parser.next().unwrap().unwrap_err();
+    let span = Span::new(Pos::new(1, 5, 4), Pos::new(1, 7, 6));
+    assert_eq!(error, ParseError::Unexpected { expected: "byte", found: "ab".to_string(), span });
+    assert_eq!(error.to_string(), "1:5: expected byte, found ab");
+}
+
+#[test]
+fn unterminated_vector_is_an_error() {
+    let tokens = vec![Ok(Lex::at(Token::VectorStart, "#(", Pos::new(1, 1, 0)))].into_iter();
+    let mut parser = Parser::new(tokens);
+    assert!(parser.next().unwrap().is_err());
+}
@@ -0,0 +1,67 @@
+/* types/src/bytevector.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use std::any::Any;
+use std::fmt;
+use object::{Obj, Object};
+
+/// A Scheme bytevector: a fixed-length sequence of bytes, written `#u8(0 1 255)`.
+#[derive(Debug, PartialEq)]
+pub struct ByteVector(Vec<u8>);
+
+impl ByteVector {
+    pub fn new(bytes: Vec<u8>) -> ByteVector {
+        ByteVector(bytes)
+    }
+
+    pub fn len(&self) -> usize { self.0.len() }
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+    pub fn bytes(&self) -> &[u8] { &self.0 }
+}
+
+impl Object for ByteVector {
+    fn as_any(&self) -> &Any { self }
+    fn as_bytevector(&self) -> Option<&ByteVector> { Some(self) }
+}
+
+impl fmt::Display for ByteVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#u8(")?;
+        for (i, byte) in self.0.iter().enumerate() {
+            let space = if i > 0 { " " } else { "" };
+            write!(f, "{}{}", space, byte)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl PartialEq<Obj> for ByteVector {
+    fn eq(&self, rhs: &Obj) -> bool {
+        match rhs.obj().and_then(Object::as_bytevector) {
+            Some(rhs_bytes) => self == rhs_bytes,
+            None => false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteVector;
+    use object::Obj;
+    use vector::Vector;
+
+    #[test]
+    fn display_bytevectors() {
+        assert_eq!(format!("{}", ByteVector::new(vec![])), "#u8()");
+        assert_eq!(format!("{}", ByteVector::new(vec![0, 12, 255])), "#u8(0 12 255)");
+    }
+
+    #[test]
+    fn eq_bytevectors() {
+        let a = Obj::new(ByteVector::new(vec![1, 2]));
+        assert_eq!(a, Obj::new(ByteVector::new(vec![1, 2])));
+        assert_ne!(a, Obj::new(ByteVector::new(vec![2, 1])));
+        assert_ne!(Obj::new(ByteVector::new(vec![])), Obj::new(Vector::empty()));
+    }
+}
@@ -0,0 +1,181 @@
+/* types/src/char/fold.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Unicode case folding, the way `string-foldcase` and the `#!fold-case` directive do it. This is
+//! the full folding from Unicode's CaseFolding.txt, so some characters fold to more than one, like
+//! `ß` to `ss`. Most characters fold the same way they lowercase; the tables here hold the ones
+//! that don't.
+
+/// Characters whose folding is a single character other than their lowercase.
+const SIMPLE: &[(char, char)] = &[
+    ('\u{00B5}', '\u{03BC}'), // MICRO SIGN
+    ('\u{017F}', 's'),        // LATIN SMALL LETTER LONG S
+    ('\u{0345}', '\u{03B9}'), // COMBINING GREEK YPOGEGRAMMENI
+    ('\u{03C2}', '\u{03C3}'), // GREEK SMALL LETTER FINAL SIGMA
+    ('\u{03D0}', '\u{03B2}'), // GREEK BETA SYMBOL
+    ('\u{03D1}', '\u{03B8}'), // GREEK THETA SYMBOL
+    ('\u{03D5}', '\u{03C6}'), // GREEK PHI SYMBOL
+    ('\u{03D6}', '\u{03C0}'), // GREEK PI SYMBOL
+    ('\u{03F0}', '\u{03BA}'), // GREEK KAPPA SYMBOL
+    ('\u{03F1}', '\u{03C1}'), // GREEK RHO SYMBOL
+    ('\u{03F5}', '\u{03B5}'), // GREEK LUNATE EPSILON SYMBOL
+    ('\u{1E9B}', '\u{1E61}'), // LATIN SMALL LETTER LONG S WITH DOT ABOVE
+    ('\u{1FBE}', '\u{03B9}'), // GREEK PROSGEGRAMMENI
+];
+
+/// Characters that fold to more than one character.
+const FULL: &[(char, &str)] = &[
+    ('\u{00DF}', "ss"),
+    ('\u{0130}', "i\u{0307}"),
+    ('\u{0149}', "\u{02BC}n"),
+    ('\u{01F0}', "j\u{030C}"),
+    ('\u{0390}', "\u{03B9}\u{0308}\u{0301}"),
+    ('\u{03B0}', "\u{03C5}\u{0308}\u{0301}"),
+    ('\u{0587}', "\u{0565}\u{0582}"),
+    ('\u{1E96}', "h\u{0331}"),
+    ('\u{1E97}', "t\u{0308}"),
+    ('\u{1E98}', "w\u{030A}"),
+    ('\u{1E99}', "y\u{030A}"),
+    ('\u{1E9A}', "a\u{02BE}"),
+    ('\u{1E9E}', "ss"),
+    ('\u{1F50}', "\u{03C5}\u{0313}"),
+    ('\u{1F52}', "\u{03C5}\u{0313}\u{0300}"),
+    ('\u{1F54}', "\u{03C5}\u{0313}\u{0301}"),
+    ('\u{1F56}', "\u{03C5}\u{0313}\u{0342}"),
+    ('\u{1FB2}', "\u{1F70}\u{03B9}"),
+    ('\u{1FB3}', "\u{03B1}\u{03B9}"),
+    ('\u{1FB4}', "\u{03AC}\u{03B9}"),
+    ('\u{1FB6}', "\u{03B1}\u{0342}"),
+    ('\u{1FB7}', "\u{03B1}\u{0342}\u{03B9}"),
+    ('\u{1FBC}', "\u{03B1}\u{03B9}"),
+    ('\u{1FC2}', "\u{1F74}\u{03B9}"),
+    ('\u{1FC3}', "\u{03B7}\u{03B9}"),
+    ('\u{1FC4}', "\u{03AE}\u{03B9}"),
+    ('\u{1FC6}', "\u{03B7}\u{0342}"),
+    ('\u{1FC7}', "\u{03B7}\u{0342}\u{03B9}"),
+    ('\u{1FCC}', "\u{03B7}\u{03B9}"),
+    ('\u{1FD2}', "\u{03B9}\u{0308}\u{0300}"),
+    ('\u{1FD3}', "\u{03B9}\u{0308}\u{0301}"),
+    ('\u{1FD6}', "\u{03B9}\u{0342}"),
+    ('\u{1FD7}', "\u{03B9}\u{0308}\u{0342}"),
+    ('\u{1FE2}', "\u{03C5}\u{0308}\u{0300}"),
+    ('\u{1FE3}', "\u{03C5}\u{0308}\u{0301}"),
+    ('\u{1FE4}', "\u{03C1}\u{0313}"),
+    ('\u{1FE6}', "\u{03C5}\u{0342}"),
+    ('\u{1FE7}', "\u{03C5}\u{0308}\u{0342}"),
+    ('\u{1FF2}', "\u{1F7C}\u{03B9}"),
+    ('\u{1FF3}', "\u{03C9}\u{03B9}"),
+    ('\u{1FF4}', "\u{03CE}\u{03B9}"),
+    ('\u{1FF6}', "\u{03C9}\u{0342}"),
+    ('\u{1FF7}', "\u{03C9}\u{0342}\u{03B9}"),
+    ('\u{1FFC}', "\u{03C9}\u{03B9}"),
+    ('\u{FB00}', "ff"),
+    ('\u{FB01}', "fi"),
+    ('\u{FB02}', "fl"),
+    ('\u{FB03}', "ffi"),
+    ('\u{FB04}', "ffl"),
+    ('\u{FB05}', "st"),
+    ('\u{FB06}', "st"),
+    ('\u{FB13}', "\u{0574}\u{0576}"),
+    ('\u{FB14}', "\u{0574}\u{0565}"),
+    ('\u{FB15}', "\u{0574}\u{056B}"),
+    ('\u{FB16}', "\u{057E}\u{0576}"),
+    ('\u{FB17}', "\u{0574}\u{056D}"),
+];
+
+/// Fold the case of `s`.
+pub fn fold_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        fold_into(c, &mut out);
+    }
+    out
+}
+
+/// Fold the case of a single character, to a single character. This is the simple folding, which
+/// leaves characters like `ß` that fold to more than one character as they are.
+pub fn fold_char(c: char) -> char {
+    if let Some(folded) = fold_special(c) {
+        return folded;
+    }
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+fn fold_into(c: char, out: &mut String) {
+    if let Ok(i) = FULL.binary_search_by_key(&c, |&(k, _)| k) {
+        out.push_str(FULL[i].1);
+    } else if let Some(letter) = iota_subscripted(c) {
+        out.push(letter);
+        out.push('\u{03B9}');
+    } else if let Some(folded) = fold_special(c) {
+        out.push(folded);
+    } else {
+        out.extend(c.to_lowercase());
+    }
+}
+
+/// The letter under the iota subscript of the Greek letters from U+1F80 to U+1FAF.
+fn iota_subscripted(c: char) -> Option<char> {
+    let bases = [0x1F00, 0x1F20, 0x1F60];
+    match c as u32 {
+        n @ 0x1F80..=0x1FAF => ::std::char::from_u32(bases[(n as usize - 0x1F80) / 16] + (n & 7)),
+        _ => None,
+    }
+}
+
+fn fold_special(c: char) -> Option<char> {
+    if let Ok(i) = SIMPLE.binary_search_by_key(&c, |&(k, _)| k) {
+        return Some(SIMPLE[i].1);
+    }
+    // Greek letters with a iota subscript fold to the letter followed by a iota, but their simple
+    // folding is the lowercase letter with the subscript.
+    match c as u32 {
+        0x1F88..=0x1F8F | 0x1F98..=0x1F9F | 0x1FA8..=0x1FAF => ::std::char::from_u32(c as u32 - 8),
+        // Cherokee is the odd one out: it folds to uppercase.
+        0x13A0..=0x13F5 => Some(c),
+        0x13F8..=0x13FD => ::std::char::from_u32(c as u32 - 8),
+        0xAB70..=0xABBF => ::std::char::from_u32(c as u32 - 0xAB70 + 0x13A0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_are_sorted() {
+        assert!(SIMPLE.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(FULL.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn folding_mostly_lowercases() {
+        assert_eq!(fold_case("Hello-World"), "hello-world");
+        assert_eq!(fold_case("ΛΑΜΒΔΑ"), "λαμβδα");
+        assert_eq!(fold_case("abc->xyz!"), "abc->xyz!");
+    }
+
+    #[test]
+    fn folding_differs_from_lowercasing() {
+        assert_eq!(fold_case("ΣΑΣ σας"), "σασ σασ");
+        assert_eq!(fold_case("Straße STRASSE"), "strasse strasse");
+        assert_eq!(fold_case("ﬁle"), "file");
+        assert_eq!(fold_case("ᏣᎳᎩ ꮳꮃꭹ"), "ᏣᎳᎩ ᏣᎳᎩ");
+        assert_eq!(fold_case("ᾈ"), "ἀι");
+    }
+
+    #[test]
+    fn single_characters() {
+        assert_eq!(fold_char('A'), 'a');
+        assert_eq!(fold_char('ς'), 'σ');
+        assert_eq!(fold_char('ß'), 'ß');
+        assert_eq!(fold_char('ᾈ'), 'ᾀ');
+        assert_eq!(fold_char('İ'), 'İ');
+    }
+}
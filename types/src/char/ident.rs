@@ -0,0 +1,190 @@
+/* types/src/char/ident.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Classification of the characters that make up identifiers, following section 7.1.1 of R7RS.
+//! Outside of ASCII, characters are classified by their Unicode general category: letters,
+//! nonspacing marks, letter-like and other numbers, connector, dash and other punctuation,
+//! symbols, and private-use characters may start an identifier, and decimal digits, spacing and
+//! enclosing marks may continue one. Brackets, quotation marks, separators, and control and
+//! format characters never appear in one.
+
+use std::cmp::Ordering;
+use char::Char;
+
+/// Ranges of non-ASCII characters that can't appear in an identifier at all, as of Unicode 14:
+/// opening, closing and quotation punctuation (Ps, Pe, Pi, Pf), separators (Zs, Zl, Zp), and
+/// control and format characters (Cc, Cf).
+const NEVER: &[(u32, u32)] = &[
+    (0x0080, 0x00A0), (0x00AB, 0x00AB), (0x00AD, 0x00AD), (0x00BB, 0x00BB), (0x0600, 0x0605),
+    (0x061C, 0x061C), (0x06DD, 0x06DD), (0x070F, 0x070F), (0x0890, 0x0891), (0x08E2, 0x08E2),
+    (0x0F3A, 0x0F3D), (0x1680, 0x1680), (0x169B, 0x169C), (0x180E, 0x180E), (0x2000, 0x200F),
+    (0x2018, 0x201F), (0x2028, 0x202F), (0x2039, 0x203A), (0x2045, 0x2046), (0x205F, 0x2064),
+    (0x2066, 0x206F), (0x207D, 0x207E), (0x208D, 0x208E), (0x2308, 0x230B), (0x2329, 0x232A),
+    (0x2768, 0x2775), (0x27C5, 0x27C6), (0x27E6, 0x27EF), (0x2983, 0x2998), (0x29D8, 0x29DB),
+    (0x29FC, 0x29FD), (0x2E02, 0x2E05), (0x2E09, 0x2E0A), (0x2E0C, 0x2E0D), (0x2E1C, 0x2E1D),
+    (0x2E20, 0x2E29), (0x2E42, 0x2E42), (0x2E55, 0x2E5C), (0x3000, 0x3000), (0x3008, 0x3011),
+    (0x3014, 0x301B), (0x301D, 0x301F), (0xFD3E, 0xFD3F), (0xFE17, 0xFE18), (0xFE35, 0xFE44),
+    (0xFE47, 0xFE48), (0xFE59, 0xFE5E), (0xFEFF, 0xFEFF), (0xFF08, 0xFF09), (0xFF3B, 0xFF3B),
+    (0xFF3D, 0xFF3D), (0xFF5B, 0xFF5B), (0xFF5D, 0xFF5D), (0xFF5F, 0xFF60), (0xFF62, 0xFF63),
+    (0xFFF9, 0xFFFB), (0x110BD, 0x110BD), (0x110CD, 0x110CD), (0x13430, 0x13438),
+    (0x1BCA0, 0x1BCA3), (0x1D173, 0x1D17A), (0xE0001, 0xE0001), (0xE0020, 0xE007F),
+];
+
+/// Ranges of non-ASCII characters that can continue an identifier but not start one, as of
+/// Unicode 14: spacing and enclosing marks (Mc, Me). Decimal digits (Nd) are the other such
+/// category; those come from `Char::digit_value`.
+const NOT_INITIAL: &[(u32, u32)] = &[
+    (0x0488, 0x0489), (0x0903, 0x0903), (0x093B, 0x093B), (0x093E, 0x0940), (0x0949, 0x094C),
+    (0x094E, 0x094F), (0x0982, 0x0983), (0x09BE, 0x09C0), (0x09C7, 0x09C8), (0x09CB, 0x09CC),
+    (0x09D7, 0x09D7), (0x0A03, 0x0A03), (0x0A3E, 0x0A40), (0x0A83, 0x0A83), (0x0ABE, 0x0AC0),
+    (0x0AC9, 0x0AC9), (0x0ACB, 0x0ACC), (0x0B02, 0x0B03), (0x0B3E, 0x0B3E), (0x0B40, 0x0B40),
+    (0x0B47, 0x0B48), (0x0B4B, 0x0B4C), (0x0B57, 0x0B57), (0x0BBE, 0x0BBF), (0x0BC1, 0x0BC2),
+    (0x0BC6, 0x0BC8), (0x0BCA, 0x0BCC), (0x0BD7, 0x0BD7), (0x0C01, 0x0C03), (0x0C41, 0x0C44),
+    (0x0C82, 0x0C83), (0x0CBE, 0x0CBE), (0x0CC0, 0x0CC4), (0x0CC7, 0x0CC8), (0x0CCA, 0x0CCB),
+    (0x0CD5, 0x0CD6), (0x0D02, 0x0D03), (0x0D3E, 0x0D40), (0x0D46, 0x0D48), (0x0D4A, 0x0D4C),
+    (0x0D57, 0x0D57), (0x0D82, 0x0D83), (0x0DCF, 0x0DD1), (0x0DD8, 0x0DDF), (0x0DF2, 0x0DF3),
+    (0x0F3E, 0x0F3F), (0x0F7F, 0x0F7F), (0x102B, 0x102C), (0x1031, 0x1031), (0x1038, 0x1038),
+    (0x103B, 0x103C), (0x1056, 0x1057), (0x1062, 0x1064), (0x1067, 0x106D), (0x1083, 0x1084),
+    (0x1087, 0x108C), (0x108F, 0x108F), (0x109A, 0x109C), (0x1715, 0x1715), (0x1734, 0x1734),
+    (0x17B6, 0x17B6), (0x17BE, 0x17C5), (0x17C7, 0x17C8), (0x1923, 0x1926), (0x1929, 0x192B),
+    (0x1930, 0x1931), (0x1933, 0x1938), (0x1A19, 0x1A1A), (0x1A55, 0x1A55), (0x1A57, 0x1A57),
+    (0x1A61, 0x1A61), (0x1A63, 0x1A64), (0x1A6D, 0x1A72), (0x1ABE, 0x1ABE), (0x1B04, 0x1B04),
+    (0x1B35, 0x1B35), (0x1B3B, 0x1B3B), (0x1B3D, 0x1B41), (0x1B43, 0x1B44), (0x1B82, 0x1B82),
+    (0x1BA1, 0x1BA1), (0x1BA6, 0x1BA7), (0x1BAA, 0x1BAA), (0x1BE7, 0x1BE7), (0x1BEA, 0x1BEC),
+    (0x1BEE, 0x1BEE), (0x1BF2, 0x1BF3), (0x1C24, 0x1C2B), (0x1C34, 0x1C35), (0x1CE1, 0x1CE1),
+    (0x1CF7, 0x1CF7), (0x20DD, 0x20E0), (0x20E2, 0x20E4), (0x302E, 0x302F), (0xA670, 0xA672),
+    (0xA823, 0xA824), (0xA827, 0xA827), (0xA880, 0xA881), (0xA8B4, 0xA8C3), (0xA952, 0xA953),
+    (0xA983, 0xA983), (0xA9B4, 0xA9B5), (0xA9BA, 0xA9BB), (0xA9BE, 0xA9C0), (0xAA2F, 0xAA30),
+    (0xAA33, 0xAA34), (0xAA4D, 0xAA4D), (0xAA7B, 0xAA7B), (0xAA7D, 0xAA7D), (0xAAEB, 0xAAEB),
+    (0xAAEE, 0xAAEF), (0xAAF5, 0xAAF5), (0xABE3, 0xABE4), (0xABE6, 0xABE7), (0xABE9, 0xABEA),
+    (0xABEC, 0xABEC), (0x11000, 0x11000), (0x11002, 0x11002), (0x11082, 0x11082),
+    (0x110B0, 0x110B2), (0x110B7, 0x110B8), (0x1112C, 0x1112C), (0x11145, 0x11146),
+    (0x11182, 0x11182), (0x111B3, 0x111B5), (0x111BF, 0x111C0), (0x111CE, 0x111CE),
+    (0x1122C, 0x1122E), (0x11232, 0x11233), (0x11235, 0x11235), (0x112E0, 0x112E2),
+    (0x11302, 0x11303), (0x1133E, 0x1133F), (0x11341, 0x11344), (0x11347, 0x11348),
+    (0x1134B, 0x1134D), (0x11357, 0x11357), (0x11362, 0x11363), (0x11435, 0x11437),
+    (0x11440, 0x11441), (0x11445, 0x11445), (0x114B0, 0x114B2), (0x114B9, 0x114B9),
+    (0x114BB, 0x114BE), (0x114C1, 0x114C1), (0x115AF, 0x115B1), (0x115B8, 0x115BB),
+    (0x115BE, 0x115BE), (0x11630, 0x11632), (0x1163B, 0x1163C), (0x1163E, 0x1163E),
+    (0x116AC, 0x116AC), (0x116AE, 0x116AF), (0x116B6, 0x116B6), (0x11720, 0x11721),
+    (0x11726, 0x11726), (0x1182C, 0x1182E), (0x11838, 0x11838), (0x11930, 0x11935),
+    (0x11937, 0x11938), (0x1193D, 0x1193D), (0x11940, 0x11940), (0x11942, 0x11942),
+    (0x119D1, 0x119D3), (0x119DC, 0x119DF), (0x119E4, 0x119E4), (0x11A39, 0x11A39),
+    (0x11A57, 0x11A58), (0x11A97, 0x11A97), (0x11C2F, 0x11C2F), (0x11C3E, 0x11C3E),
+    (0x11CA9, 0x11CA9), (0x11CB1, 0x11CB1), (0x11CB4, 0x11CB4), (0x11D8A, 0x11D8E),
+    (0x11D93, 0x11D94), (0x11D96, 0x11D96), (0x11EF5, 0x11EF6), (0x16F51, 0x16F87),
+    (0x16FF0, 0x16FF1), (0x1D165, 0x1D166), (0x1D16D, 0x1D172),
+];
+
+/// True if `c` can start an identifier.
+pub fn is_initial(c: char) -> bool {
+    if c.is_ascii() {
+        c.is_ascii_alphabetic() || is_special_initial(c)
+    } else {
+        !in_ranges(NEVER, c) && !in_ranges(NOT_INITIAL, c) && Char(c).digit_value().is_none()
+    }
+}
+
+/// True if `c` can appear in an identifier after the first character.
+pub fn is_subsequent(c: char) -> bool {
+    if c.is_ascii() {
+        is_initial(c) || c.is_ascii_digit() || is_special_subsequent(c)
+    } else {
+        !in_ranges(NEVER, c)
+    }
+}
+
+fn in_ranges(ranges: &[(u32, u32)], c: char) -> bool {
+    let n = c as u32;
+    ranges.binary_search_by(|&(first, last)| {
+        if last < n {
+            Ordering::Less
+        } else if first > n {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }).is_ok()
+}
+
+pub fn is_special_initial(c: char) -> bool {
+    "!$%&*/:<=>?^_~".contains(c)
+}
+
+pub fn is_special_subsequent(c: char) -> bool {
+    is_explicit_sign(c) || c == '.' || c == '@'
+}
+
+pub fn is_explicit_sign(c: char) -> bool {
+    c == '+' || c == '-'
+}
+
+/// True if `c` can follow the sign at the start of a peculiar identifier like `->x`.
+pub fn is_sign_subsequent(c: char) -> bool {
+    is_initial(c) || is_explicit_sign(c) || c == '@'
+}
+
+/// True if `c` can follow the dot at the start of a peculiar identifier like `...` or `+.foo`.
+pub fn is_dot_subsequent(c: char) -> bool {
+    is_sign_subsequent(c) || c == '.'
+}
+
+/// True if `name` can be written without `|` quotes and read back as the same identifier. This is
+/// false for names with characters that can't appear in an identifier, and for names that would
+/// read as something else, like a number or the dot of a dotted pair.
+pub fn is_plain(name: &str) -> bool {
+    let chars: Vec<char> = name.chars().collect();
+    let rest_are_subsequent = |from: usize| chars.iter().skip(from).all(|c| is_subsequent(*c));
+    match chars.first() {
+        None => false,
+        Some(&c) if is_initial(c) => rest_are_subsequent(1),
+        Some(&c) if is_explicit_sign(c) => match chars.get(1) {
+            None => true,
+            Some(&'.') => matches!(chars.get(2), Some(&c) if is_dot_subsequent(c))
+                          && rest_are_subsequent(3),
+            Some(&c) if is_sign_subsequent(c) => {
+                rest_are_subsequent(2) && !is_number_like(&name[1..])
+            },
+            _ => false,
+        },
+        Some(&'.') => matches!(chars.get(1), Some(&c) if is_dot_subsequent(c)) && rest_are_subsequent(2),
+        _ => false,
+    }
+}
+
+/// True if `s`, following a sign, would be read as the imaginary unit, an infinity or NaN, or a
+/// complex number starting with one of those.
+fn is_number_like(s: &str) -> bool {
+    let s = s.to_lowercase();
+    if s == "i" {
+        return true;
+    }
+    ["inf.0", "nan.0"].iter().any(|special| {
+        s.starts_with(special)
+            && matches!(s[special.len()..].chars().next(), None | Some('+') | Some('-') | Some('@') | Some('i'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_identifiers() {
+        for name in &["abc", "a1", "list->vector", "+", "-", "...", "->x", "+.foo", "..",
+                      "+in", "+inf.0x", "λ", "straße", "x²", "²x", "€", "a—b",
+                      "\u{301}x", "x\u{903}", "x\u{20DD}", "x٣"] {
+            assert!(is_plain(name), "{} should be plain", name);
+        }
+    }
+
+    #[test]
+    fn identifiers_that_need_pipes() {
+        for name in &["", "hello world", "1abc", ".", "+1", "+.5", "+i", "-inf.0", "+nan.0i",
+                      "a|b", "a(b", "#foo", "+.", "«a", "a«", "a»",
+                      "\u{903}x", "\u{20DD}x", "٣x", "a\u{A0}b", "a\u{200B}b", "a\u{85}b"] {
+            assert!(!is_plain(name), "{} should need pipes", name);
+        }
+    }
+}
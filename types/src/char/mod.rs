@@ -1,71 +1,220 @@
-/* types/char.rs
+/* types/src/char/mod.rs
  * Eryn Wells <eryn@erynwells.me>
  */
 
-mod names;
+//! # Characters
+//!
+//! Scheme characters, the table of character names used when reading and writing them, case
+//! folding, and the classification of characters in identifiers.
 
-use object::Object;
-use predicates::IsChar;
+pub mod fold;
+pub mod ident;
+pub mod names;
 
-impl IsChar for Object {
-    fn is_char(&self) -> bool {
-        match *self {
-            Object::Char(_) => true,
-            _ => false,
+use std::any::Any;
+use std::char as stdchar;
+use std::cmp::Ordering;
+use std::fmt;
+use object::{Obj, Object};
+
+/// The code points of the zero digits of Unicode's decimal digits, general category Nd, as of
+/// Unicode 14. Each zero is followed by the other nine digits in order.
+const DIGIT_ZEROS: &[u32] = &[
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66,
+    0x0CE6, 0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946,
+    0x19D0, 0x1A80, 0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0,
+    0xA9F0, 0xAA50, 0xABF0, 0xFF10, 0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0,
+    0x11450, 0x114D0, 0x11650, 0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0,
+    0x16A60, 0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0,
+    0x1E950, 0x1FBF0,
+];
+
+/// A Scheme character, which is any Unicode scalar value. The predicates and case conversions
+/// follow section 6.6 of R7RS, using Unicode's properties.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Char(pub char);
+
+impl Char {
+    /// The character named `name`, like `newline`.
+    pub fn named(name: &str) -> Option<Char> {
+        names::char_for(name).map(Char)
+    }
+
+    /// The character with the scalar value `n`. This is Scheme's `integer->char`.
+    pub fn from_u32(n: u32) -> Option<Char> {
+        stdchar::from_u32(n).map(Char)
+    }
+
+    /// The name of the character, if it has one.
+    pub fn name(&self) -> Option<&'static str> {
+        names::name_of(self.0)
+    }
+
+    /// The scalar value of the character. This is Scheme's `char->integer`.
+    pub fn to_u32(&self) -> u32 {
+        self.0 as u32
+    }
+
+    /// `char-alphabetic?`
+    pub fn is_alphabetic(&self) -> bool { self.0.is_alphabetic() }
+    /// `char-numeric?`, which is true of the decimal digits of any script.
+    pub fn is_numeric(&self) -> bool { self.digit_value().is_some() }
+    /// `char-whitespace?`
+    pub fn is_whitespace(&self) -> bool { self.0.is_whitespace() }
+    /// `char-upper-case?`
+    pub fn is_upper_case(&self) -> bool { self.0.is_uppercase() }
+    /// `char-lower-case?`
+    pub fn is_lower_case(&self) -> bool { self.0.is_lowercase() }
+
+    /// The value of the character if it's a decimal digit, in any script. This is Scheme's
+    /// `digit-value`.
+    pub fn digit_value(&self) -> Option<u32> {
+        let n = self.to_u32();
+        let zero = match DIGIT_ZEROS.binary_search(&n) {
+            Ok(i) => DIGIT_ZEROS[i],
+            Err(0) => return None,
+            Err(i) => DIGIT_ZEROS[i - 1],
+        };
+        if n - zero < 10 { Some(n - zero) } else { None }
+    }
+
+    /// `char-upcase`. Characters whose uppercase is more than one character, like `ß`, stay the
+    /// same.
+    pub fn upcase(&self) -> Char {
+        Char::single(self.0.to_uppercase()).unwrap_or(*self)
+    }
+
+    /// `char-downcase`
+    pub fn downcase(&self) -> Char {
+        Char::single(self.0.to_lowercase()).unwrap_or(*self)
+    }
+
+    /// `char-foldcase`
+    pub fn foldcase(&self) -> Char {
+        Char(fold::fold_char(self.0))
+    }
+
+    /// Compare two characters ignoring case, like `char-ci<?` and friends.
+    pub fn cmp_ci(&self, other: &Char) -> Ordering {
+        self.foldcase().cmp(&other.foldcase())
+    }
+
+    fn single<I>(mut chars: I) -> Option<Char> where I: Iterator<Item=char> {
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(Char(c)),
+            _ => None,
         }
     }
 }
 
-pub trait Char {
-    fn from_char(c: char) -> Object;
-    fn from_char_named(name: &str) -> Option<Object>;
-    fn char_name(&self) -> Option<String>;
+impl Object for Char {
+    fn as_any(&self) -> &Any { self }
+    fn as_char(&self) -> Option<&Char> { Some(self) }
 }
 
-impl Char for Object {
-    fn from_char(c: char) -> Object {
-        Object::Char(c)
+impl fmt::Display for Char {
+    /// Write the character so it reads back as the same character: by name if it has one, as
+    /// itself if it's printable ASCII, and as a hex scalar value otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "#\\{}", name),
+            None if self.0.is_ascii_graphic() => write!(f, "#\\{}", self.0),
+            None => write!(f, "#\\x{:x}", self.to_u32()),
+        }
     }
+}
 
-    fn from_char_named(name: &str) -> Option<Object> {
-        names::char_for(name).map(|c| Object::from_char(c))
+impl From<char> for Char {
+    fn from(c: char) -> Char {
+        Char(c)
     }
+}
 
-    fn char_name(&self) -> Option<String> {
-        match *self {
-            Object::Char(c) => Some(names::name_of(c).map_or(c.to_string(), String::from)),
-            _ => None,
+impl PartialEq<Obj> for Char {
+    fn eq(&self, rhs: &Obj) -> bool {
+        match rhs.obj().and_then(Object::as_char) {
+            Some(rhs_char) => self == rhs_char,
+            None => false
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use object::Object;
-    use predicates::{IsBool, IsChar};
-    use super::Char;
+    use super::*;
+    use bool::Bool;
 
     #[test]
     fn chars_are_chars() {
-        assert_eq!(Object::from_char('a').is_char(), true);
-        assert_eq!(Object::from_char('a').is_bool(), false);
+        let a = Obj::new(Char('a'));
+        assert_eq!(a.obj().and_then(Object::as_char), Some(&Char('a')));
+        assert!(Obj::new(Bool::True).obj().and_then(Object::as_char).is_none());
+        assert_eq!(a, Obj::new(Char('a')));
+        assert_ne!(a, Obj::new(Char('b')));
+    }
+
+    #[test]
+    fn named_chars() {
+        assert_eq!(Char::named("newline"), Some(Char('\n')));
+        assert_eq!(Char::named("asdf"), None);
+        assert_eq!(Char('\x7F').name(), Some("delete"));
+        assert_eq!(Char('a').name(), None);
+    }
+
+    #[test]
+    fn display_in_write_form() {
+        assert_eq!(Char('a').to_string(), "#\\a");
+        assert_eq!(Char('(').to_string(), "#\\(");
+        assert_eq!(Char('\n').to_string(), "#\\newline");
+        assert_eq!(Char(' ').to_string(), "#\\space");
+        assert_eq!(Char('λ').to_string(), "#\\x3bb");
+        assert_eq!(Char('\x01').to_string(), "#\\x1");
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(Char('λ').to_u32(), 0x3BB);
+        assert_eq!(Char::from_u32(0x41), Some(Char('A')));
+        assert_eq!(Char::from_u32(0xD800), None);
+    }
+
+    #[test]
+    fn predicates() {
+        assert!(Char('a').is_alphabetic() && Char('λ').is_alphabetic());
+        assert!(!Char('1').is_alphabetic());
+        assert!(Char('\u{3000}').is_whitespace() && Char('\t').is_whitespace());
+        assert!(Char('A').is_upper_case() && !Char('A').is_lower_case());
+        assert!(Char('ß').is_lower_case());
+        assert!(!Char('1').is_upper_case() && !Char('1').is_lower_case());
     }
 
     #[test]
-    fn equal_chars_are_equal() {
-        assert_eq!(Object::from_char('a'), Object::from_char('a'));
+    fn digits_in_any_script() {
+        assert_eq!(Char('3').digit_value(), Some(3));
+        assert_eq!(Char('\u{0664}').digit_value(), Some(4));
+        assert_eq!(Char('\u{0AE6}').digit_value(), Some(0));
+        assert_eq!(Char('\u{1D7FF}').digit_value(), Some(9));
+        assert_eq!(Char('a').digit_value(), None);
+        assert_eq!(Char('²').digit_value(), None);
+        assert!(Char('\u{0664}').is_numeric());
+        assert!(!Char('\u{00BD}').is_numeric());
     }
 
     #[test]
-    fn named_chars_are_created() {
-        assert_eq!(Object::from_char_named("newline"), Some(Object::from_char('\n')));
-        assert_eq!(Object::from_char_named("asdf"), None);
+    fn case_conversion() {
+        assert_eq!(Char('a').upcase(), Char('A'));
+        assert_eq!(Char('Λ').downcase(), Char('λ'));
+        assert_eq!(Char('ß').upcase(), Char('ß'));
+        assert_eq!(Char('İ').downcase(), Char('İ'));
+        assert_eq!(Char('Σ').foldcase(), Char('σ'));
+        assert_eq!(Char('ς').foldcase(), Char('σ'));
+        assert_eq!(Char('1').upcase(), Char('1'));
     }
 
     #[test]
-    fn chars_have_names() {
-        assert_eq!(Object::from_char('a').char_name(), Some(String::from("a")));
-        assert_eq!(Object::from_char('\n').char_name(), Some(String::from("newline")));
-        assert_eq!(Object::Bool(true).char_name(), None);
+    fn case_insensitive_comparison() {
+        assert_eq!(Char('a').cmp_ci(&Char('A')), Ordering::Equal);
+        assert_eq!(Char('a').cmp_ci(&Char('B')), Ordering::Less);
+        assert!(Char('B') < Char('a'));
     }
 }
@@ -4,7 +4,7 @@
 
 use std::collections::HashMap;
 use std::mem;
-use std::sync::{Once, ONCE_INIT};
+use std::sync::Once;
 
 const ALARM: &'static str = "alarm";
 const BACKSPACE: &'static str = "backspace";
@@ -20,7 +20,7 @@ const TAB: &'static str = "tab";
 /// Otherwise, returns `None`.
 pub fn char_for(name: &str) -> Option<char> {
     type NameMap = HashMap<&'static str, char>;
-    static ONCE: Once = ONCE_INIT;
+    static ONCE: Once = Once::new();
     static mut NAMES_TO_CHARS: *const NameMap = 0 as *const NameMap;
     unsafe {
         ONCE.call_once(|| {
@@ -44,7 +44,7 @@ pub fn char_for(name: &str) -> Option<char> {
 /// Otherwise, returns `None`.
 pub fn name_of(c: char) -> Option<&'static str> {
     type CharMap = HashMap<char, &'static str>;
-    static ONCE: Once = ONCE_INIT;
+    static ONCE: Once = Once::new();
     static mut CHARS_TO_NAMES: *const CharMap = 0 as *const CharMap;
     unsafe {
         ONCE.call_once(|| {
@@ -63,3 +63,22 @@ pub fn name_of(c: char) -> Option<&'static str> {
         (*CHARS_TO_NAMES).get(&c).map(|s| *s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_map_to_chars() {
+        assert_eq!(char_for("newline"), Some('\n'));
+        assert_eq!(char_for("alarm"), Some('\x07'));
+        assert_eq!(char_for("asdf"), None);
+    }
+
+    #[test]
+    fn chars_map_to_names() {
+        assert_eq!(name_of('\x7F'), Some("delete"));
+        assert_eq!(name_of('\0'), Some("null"));
+        assert_eq!(name_of('a'), None);
+    }
+}
@@ -0,0 +1,199 @@
+/* types/src/heap.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # The Heap
+//!
+//! Objects are reference counted, which frees most of them as soon as the last `Obj` referring to
+//! them goes away. Reference counting can't free a cycle though: each object in it is kept alive
+//! by the one before it. The heap keeps track of every object so it can find cycles that nothing
+//! outside the heap refers to any more, and break them.
+//!
+//! Finding them works like this. For each object, count the references to it from other objects
+//! on the heap. An object with more references than that is referred to from outside the heap,
+//! by a variable or a Rust data structure, so it's a root. Everything reachable from the roots is
+//! alive; everything else is garbage, and is only being kept alive by other garbage. The
+//! collector asks each garbage object to `unlink` its references, which lets reference counting
+//! free the lot.
+//!
+//! Collection happens on its own as objects are made, or can be asked for with `collect`. The
+//! heap belongs to the thread, like the objects on it.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use object::{addr_of, Obj, Object};
+
+/// How many objects to make before the first automatic collection.
+const FIRST_COLLECTION: usize = 10_000;
+
+struct Heap {
+    objects: Vec<Weak<Object>>,
+    /// Make this many more objects before collecting automatically.
+    until_collection: usize,
+}
+
+thread_local! {
+    static HEAP: RefCell<Heap> = RefCell::new(Heap {
+        objects: Vec::new(),
+        until_collection: FIRST_COLLECTION,
+    });
+}
+
+/// Keep track of a newly made object, and collect garbage if it's time to.
+pub(crate) fn register(obj: &Rc<Object>) {
+    let time_to_collect = HEAP.with(|heap| {
+        let mut heap = heap.borrow_mut();
+        heap.objects.push(Rc::downgrade(obj));
+        heap.until_collection = heap.until_collection.saturating_sub(1);
+        heap.until_collection == 0
+    });
+    if time_to_collect {
+        collect();
+    }
+}
+
+/// Free every object that's only kept alive by cycles of garbage. Returns how many objects were
+/// freed.
+pub fn collect() -> usize {
+    // Hold on to every live object while collecting, so nothing is freed partway through.
+    let live: Vec<Rc<Object>> = HEAP.with(|heap| {
+        let mut heap = heap.borrow_mut();
+        let live: Vec<Rc<Object>> = heap.objects.iter().filter_map(Weak::upgrade).collect();
+        heap.objects = live.iter().map(Rc::downgrade).collect();
+        live
+    });
+    let index: HashMap<usize, usize> = live.iter()
+        .enumerate()
+        .map(|(i, obj)| (addr_of(&**obj), i))
+        .collect();
+    let lookup = |obj: &Obj| obj.addr().and_then(|addr| index.get(&addr).cloned());
+
+    // Count the references to each object from other objects on the heap.
+    let mut internal = vec![0; live.len()];
+    for obj in &live {
+        obj.trace(&mut |child| if let Some(i) = lookup(child) { internal[i] += 1; });
+    }
+
+    // Anything with references from outside the heap is a root. `live` holds one of those.
+    let mut reachable = vec![false; live.len()];
+    let mut pending: Vec<usize> = (0..live.len())
+        .filter(|&i| Rc::strong_count(&live[i]) > internal[i] + 1)
+        .collect();
+    while let Some(i) = pending.pop() {
+        if reachable[i] {
+            continue;
+        }
+        reachable[i] = true;
+        live[i].trace(&mut |child| if let Some(j) = lookup(child) {
+            if !reachable[j] {
+                pending.push(j);
+            }
+        });
+    }
+
+    let mut freed = 0;
+    for (obj, _) in live.iter().zip(reachable.iter()).filter(|&(_, reachable)| !reachable) {
+        obj.unlink();
+        freed += 1;
+    }
+
+    HEAP.with(|heap| {
+        let mut heap = heap.borrow_mut();
+        heap.objects = live.iter()
+            .zip(reachable.iter())
+            .filter(|&(_, reachable)| *reachable)
+            .map(|(obj, _)| Rc::downgrade(obj))
+            .collect();
+        // Wait for the heap to grow by as much again as what's on it now, so collecting doesn't
+        // take more than a constant amount of time per object made.
+        heap.until_collection = FIRST_COLLECTION.max(live.len() - freed);
+    });
+    freed
+}
+
+/// The number of objects on the heap. Objects freed since the last collection may still be
+/// counted.
+pub fn len() -> usize {
+    HEAP.with(|heap| heap.borrow().objects.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pair::Pair;
+    use sym::Sym;
+    use vector::Vector;
+
+    fn sym(name: &str) -> Obj {
+        Obj::new(Sym::with_str(name))
+    }
+
+    /// A reference to `obj` that doesn't keep it alive.
+    fn weak(obj: &Obj) -> Weak<Object> {
+        match *obj {
+            Obj::Ptr(ref rc) => Rc::downgrade(rc),
+            Obj::Null => panic!("the empty list isn't on the heap"),
+        }
+    }
+
+    #[test]
+    fn unreachable_cycles_are_freed() {
+        collect();
+        let a = Obj::new(Pair::with_car(sym("a")));
+        let b = Obj::new(Pair::new(sym("b"), a.clone()));
+        a.obj().and_then(Object::as_pair).unwrap().set_cdr(b.clone());
+        let (weak_a, weak_b) = (weak(&a), weak(&b));
+        drop(a);
+        drop(b);
+        assert!(weak_a.upgrade().is_some());
+        // The pairs, and the symbols in them.
+        assert_eq!(collect(), 4);
+        assert!(weak_a.upgrade().is_none());
+        assert!(weak_b.upgrade().is_none());
+    }
+
+    #[test]
+    fn reachable_cycles_are_kept() {
+        let a = Obj::new(Pair::with_car(sym("a")));
+        a.obj().and_then(Object::as_pair).unwrap().set_cdr(a.clone());
+        let v = Obj::new(Vector::new(vec![a.clone()]));
+        drop(a);
+        collect();
+        let a = v.obj().and_then(Object::as_vector).unwrap().get(0).unwrap();
+        assert_eq!(a.to_string(), "#0=(a . #0#)");
+    }
+
+    #[test]
+    fn shared_structure_is_kept() {
+        let tail = Obj::new(Pair::with_car(sym("c")));
+        let a = Obj::new(Pair::new(sym("a"), tail.clone()));
+        let b = Obj::new(Pair::new(sym("b"), tail));
+        collect();
+        assert_eq!(a.to_string(), "(a c)");
+        assert_eq!(b.to_string(), "(b c)");
+    }
+
+    #[test]
+    fn cycles_through_vectors_are_freed() {
+        collect();
+        let v = Obj::new(Vector::new(vec![Obj::Null]));
+        let p = Obj::new(Pair::new(v.clone(), Obj::Null));
+        v.obj().and_then(Object::as_vector).unwrap().set(0, p.clone());
+        let weak_v = weak(&v);
+        drop(v);
+        drop(p);
+        assert_eq!(collect(), 2);
+        assert!(weak_v.upgrade().is_none());
+    }
+
+    #[test]
+    fn collection_happens_on_its_own() {
+        collect();
+        for _ in 0..FIRST_COLLECTION {
+            let a = Obj::new(Pair::empty());
+            a.obj().and_then(Object::as_pair).unwrap().set_car(a.clone());
+        }
+        assert!(len() < FIRST_COLLECTION);
+    }
+}
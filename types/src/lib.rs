@@ -1,13 +1,23 @@
 mod bool;
+mod bytevector;
+pub mod char;
+pub mod heap;
 mod number;
 mod object;
 mod pair;
+mod printer;
+mod string;
 mod sym;
+mod vector;
 
 pub use bool::Bool;
+pub use bytevector::ByteVector;
+pub use char::Char;
 pub use object::Obj;
 pub use pair::Pair;
+pub use string::Str;
 pub use sym::Sym;
+pub use vector::Vector;
 
 pub use self::number::Number;
-pub use self::number::Int;
+pub use self::number::{Frac, Int, Irr, Real};
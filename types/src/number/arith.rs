@@ -10,6 +10,20 @@ pub trait GCD {
     fn gcd(self, other: Self) -> Self;
 }
 
+/// Wide integers, for doing arithmetic on the parts of numbers without overflowing. The GCD is
+/// never negative.
+impl GCD for i128 {
+    fn gcd(self, other: i128) -> i128 {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while b != 0 {
+            let r = a % b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+}
+
 pub trait LCM {
     /// Find the least common multiple of `self` and another number.
     fn lcm(self, other: Self) -> Self;
@@ -4,13 +4,17 @@
 
 use std::any::Any;
 use std::fmt;
-use std::ops::{Add, Mul};
+use std::cmp::Ordering;
+use std::ops::{Add, Div, Mul, Sub};
 use number::arith::GCD;
-use number::{Int, Number};
+use number::{Int, Number, Real};
 use object::{Obj, Object};
 
-/// A fraction of two integers.
-#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+/// A fraction of two integers, in lowest terms, with a positive denominator.
+///
+/// Arithmetic on fractions gives `None` if the result doesn't fit in a `Frac`, or when dividing
+/// by zero. `Real` does arithmetic that falls back to inexact numbers instead.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct Frac {
     /// The numerator.
     p: Int,
@@ -19,62 +23,105 @@ pub struct Frac {
 }
 
 impl Frac {
-    pub fn new(p: Int, q: Int) -> Result<Frac, ()> {
-        if q.is_zero() {
-            // TODO: Return a more specific error about dividing by zero.
-            Err(())
-        } else {
-            Ok(Frac{p, q}.reduced())
-        }
+    /// The fraction `p/q` in lowest terms, or `None` if `q` is zero or the reduced fraction
+    /// doesn't fit.
+    pub fn new(p: Int, q: Int) -> Option<Frac> {
+        Frac::reduced(p.0 as i128, q.0 as i128)
     }
 
-    pub fn from_ints(p: i64, q: i64) -> Result<Frac, ()> {
+    pub fn from_ints(p: i64, q: i64) -> Option<Frac> {
         Frac::new(Int(p), Int(q))
     }
 
+    pub fn numerator(&self) -> Int { self.p }
+    pub fn denominator(&self) -> Int { self.q }
+
     pub fn quotient(&self) -> f64 {
         self.p.0 as f64 / self.q.0 as f64
     }
 
-    fn reduced(self) -> Frac {
-        let gcd = self.p.gcd(self.q);
-        Frac { p: self.p / gcd, q: self.q / gcd }
+    /// Reduce `p/q` to lowest terms, with the sign on top. Returns `None` if `q` is zero, or if
+    /// the reduced fraction doesn't fit.
+    pub(crate) fn reduced(p: i128, q: i128) -> Option<Frac> {
+        if q == 0 {
+            return None;
+        }
+        let gcd = p.gcd(q);
+        let (p, q) = if q < 0 { (-p / gcd, -q / gcd) } else { (p / gcd, q / gcd) };
+        if p < i64::MIN as i128 || p > i64::MAX as i128 || q > i64::MAX as i128 {
+            return None;
+        }
+        Some(Frac { p: Int(p as i64), q: Int(q as i64) })
+    }
+
+    /// The parts of two fractions, widened so multiplying any two of them can't overflow.
+    fn widened(self, rhs: Frac) -> (i128, i128, i128, i128) {
+        (self.p.0 as i128, self.q.0 as i128, rhs.p.0 as i128, rhs.q.0 as i128)
+    }
+
+    fn _add(self, rhs: Frac) -> Option<Frac> {
+        let (p, q, rp, rq) = self.widened(rhs);
+        Frac::reduced(p * rq + rp * q, q * rq)
+    }
+
+    fn _sub(self, rhs: Frac) -> Option<Frac> {
+        let (p, q, rp, rq) = self.widened(rhs);
+        Frac::reduced(p * rq - rp * q, q * rq)
     }
 
-    fn _add(self, rhs: Frac) -> Frac {
-        let p = self.p * rhs.q + rhs.p * self.q;
-        let q = self.q * rhs.q;
-        Frac{p,q}.reduced()
+    fn _mul(self, rhs: Frac) -> Option<Frac> {
+        let (p, q, rp, rq) = self.widened(rhs);
+        Frac::reduced(p * rp, q * rq)
     }
 
-    fn _mul(self, rhs: Frac) -> Frac {
-        let p = self.p * rhs.p;
-        let q = self.q * rhs.q;
-        Frac{p,q}.reduced()
+    fn _div(self, rhs: Frac) -> Option<Frac> {
+        let (p, q, rp, rq) = self.widened(rhs);
+        Frac::reduced(p * rq, q * rp)
     }
 }
 
 impl Add for Frac {
-    type Output = Frac;
+    type Output = Option<Frac>;
     fn add(self, rhs: Self) -> Self::Output {
         self._add(rhs)
     }
 }
 
-impl<'a> Add<Frac> for &'a Frac {
-    type Output = Frac;
+impl Add<Frac> for &Frac {
+    type Output = Option<Frac>;
     fn add(self, rhs: Frac) -> Self::Output {
         self._add(rhs)
     }
 }
 
-impl<'a, 'b> Add<&'a Frac> for &'b Frac {
-    type Output = Frac;
+impl Add<&Frac> for &Frac {
+    type Output = Option<Frac>;
     fn add(self, rhs: &Frac) -> Self::Output {
         self._add(*rhs)
     }
 }
 
+impl Div for Frac {
+    type Output = Option<Frac>;
+    fn div(self, rhs: Self) -> Self::Output {
+        self._div(rhs)
+    }
+}
+
+impl Div<Frac> for &Frac {
+    type Output = Option<Frac>;
+    fn div(self, rhs: Frac) -> Self::Output {
+        self._div(rhs)
+    }
+}
+
+impl Div<&Frac> for &Frac {
+    type Output = Option<Frac>;
+    fn div(self, rhs: &Frac) -> Self::Output {
+        self._div(*rhs)
+    }
+}
+
 impl fmt::Display for Frac {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}/{}", self.p, self.q)
@@ -88,26 +135,60 @@ impl From<Int> for Frac {
 }
 
 impl Mul for Frac {
-    type Output = Frac;
+    type Output = Option<Frac>;
     fn mul(self, rhs: Self) -> Self::Output {
         self._mul(rhs)
     }
 }
 
-impl<'a> Mul<Frac> for &'a Frac {
-    type Output = Frac;
+impl Mul<Frac> for &Frac {
+    type Output = Option<Frac>;
     fn mul(self, rhs: Frac) -> Self::Output {
         self._mul(rhs)
     }
 }
 
-impl<'a, 'b> Mul<&'a Frac> for &'b Frac {
-    type Output = Frac;
+impl Mul<&Frac> for &Frac {
+    type Output = Option<Frac>;
     fn mul(self, rhs: &Frac) -> Self::Output {
         self._mul(*rhs)
     }
 }
 
+impl Ord for Frac {
+    fn cmp(&self, rhs: &Frac) -> Ordering {
+        // Denominators are positive, so cross multiplying keeps the order. i128 can't overflow.
+        (self.p.0 as i128 * rhs.q.0 as i128).cmp(&(rhs.p.0 as i128 * self.q.0 as i128))
+    }
+}
+
+impl PartialOrd for Frac {
+    fn partial_cmp(&self, rhs: &Frac) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl Sub for Frac {
+    type Output = Option<Frac>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self._sub(rhs)
+    }
+}
+
+impl Sub<Frac> for &Frac {
+    type Output = Option<Frac>;
+    fn sub(self, rhs: Frac) -> Self::Output {
+        self._sub(rhs)
+    }
+}
+
+impl Sub<&Frac> for &Frac {
+    type Output = Option<Frac>;
+    fn sub(self, rhs: &Frac) -> Self::Output {
+        self._sub(*rhs)
+    }
+}
+
 impl Number for Frac {
     fn as_int(&self) -> Option<Int> {
         if self.q == Int(1) {
@@ -117,7 +198,9 @@ impl Number for Frac {
         }
     }
 
-    fn as_frac(&self) -> Option<Frac> { Frac::new(self.p, self.q).ok() }
+    fn as_frac(&self) -> Option<Frac> { Frac::new(self.p, self.q) }
+
+    fn as_real(&self) -> Real { Real::Rational(*self) }
 
     fn is_zero(&self) -> bool { self.p.is_zero() }
 }
@@ -138,10 +221,7 @@ impl PartialEq<Obj> for Frac {
 
 impl<'a> PartialEq<Number + 'a> for Frac {
     fn eq(&self, rhs: &(Number + 'a)) -> bool {
-        match rhs.as_frac() {
-            Some(rhs) => *self == rhs,
-            None => false
-        }
+        self.as_real().eqv(&rhs.as_real())
     }
 }
 
@@ -152,7 +232,7 @@ mod tests {
 
     #[test]
     fn fracs_with_zero_q_are_invalid() {
-        assert!(Frac::from_ints(3, 0).is_err())
+        assert!(Frac::from_ints(3, 0).is_none())
     }
 
     #[test]
@@ -184,7 +264,7 @@ mod tests {
         let a = Frac::from_ints(5, 6).unwrap();
         let b = Frac::from_ints(2, 3).unwrap();
         let r = Frac::from_ints(3, 2).unwrap();
-        assert_eq!(a + b, r);
+        assert_eq!(a + b, Some(r));
     }
 
     #[test]
@@ -192,6 +272,68 @@ mod tests {
         let a = Frac::from_ints(4, 3).unwrap();
         let b = Frac::from_ints(3, 8).unwrap();
         let r = Frac::from_ints(1, 2).unwrap();
-        assert_eq!(a * b, r);
+        assert_eq!(a * b, Some(r));
+    }
+
+    #[test]
+    fn fracs_can_subtract() {
+        let a = Frac::from_ints(1, 2).unwrap();
+        let b = Frac::from_ints(2, 3).unwrap();
+        let r = Frac::from_ints(-1, 6).unwrap();
+        assert_eq!(a - b, Some(r));
+    }
+
+    #[test]
+    fn fracs_can_divide() {
+        let a = Frac::from_ints(3, 4).unwrap();
+        let b = Frac::from_ints(-3, 8).unwrap();
+        let r = Frac::from_ints(-2, 1).unwrap();
+        assert_eq!(a / b, Some(r));
+    }
+
+    #[test]
+    fn frac_arithmetic_that_overflows() {
+        let third = Frac::from_ints(1, 3).unwrap();
+        let tiny = Frac::from_ints(1, i64::MAX).unwrap();
+        assert_eq!(third - tiny, None);
+        assert_eq!(third * tiny, None);
+        assert_eq!(tiny + tiny, Frac::from_ints(2, i64::MAX));
+    }
+
+    #[test]
+    fn fracs_divided_by_zero() {
+        let third = Frac::from_ints(1, 3).unwrap();
+        assert_eq!(third / Frac::from(Int(0)), None);
+    }
+
+    #[test]
+    fn fracs_keep_the_sign_on_top() {
+        let fr = Frac::from_ints(3, -6).unwrap();
+        assert_eq!(fr.numerator(), Int(-1));
+        assert_eq!(fr.denominator(), Int(2));
+        assert_eq!(fr.to_string(), "-1/2");
+    }
+
+    #[test]
+    fn fracs_of_the_smallest_int() {
+        let fr = Frac::from_ints(i64::MIN, 5).unwrap();
+        assert_eq!(fr.numerator(), Int(i64::MIN));
+        assert_eq!(Frac::from_ints(i64::MIN, -2).unwrap().numerator(), Int(i64::MIN / -2));
+        assert!(Frac::from_ints(i64::MIN, -1).is_none());
+    }
+
+    #[test]
+    fn fracs_order_by_value() {
+        let half = Frac::from_ints(1, 2).unwrap();
+        let third = Frac::from_ints(1, 3).unwrap();
+        let neg = Frac::from_ints(-5, 2).unwrap();
+        assert!(third < half);
+        assert!(neg < third);
+    }
+
+    #[test]
+    fn fracs_equal_ints_with_the_same_value() {
+        assert_eq!(Obj::new(Frac::from_ints(4, 2).unwrap()), Obj::new(Int(2)));
+        assert_eq!(Obj::new(Int(2)), Obj::new(Frac::from_ints(4, 2).unwrap()));
     }
 }
@@ -6,7 +6,7 @@ use std::any::Any;
 use std::fmt;
 use number::arith::{GCD, LCM};
 use object::{Obj, Object};
-use super::{Frac, Number};
+use super::{Frac, Number, Real};
 
 #[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Int(pub i64);
@@ -54,7 +54,8 @@ impl Object for Int {
 
 impl Number for Int {
     fn as_int(&self) -> Option<Int> { Some(*self) }
-    fn as_frac(&self) -> Option<Frac> { Frac::new(*self, Int(1)).ok() }
+    fn as_frac(&self) -> Option<Frac> { Frac::new(*self, Int(1)) }
+    fn as_real(&self) -> Real { Real::Integer(*self) }
     fn is_zero(&self) -> bool { self.0 == 0 }
 }
 
@@ -69,10 +70,7 @@ impl PartialEq<Obj> for Int {
 
 impl<'a> PartialEq<Number + 'a> for Int {
     fn eq(&self, rhs: &(Number + 'a)) -> bool {
-        match rhs.as_int() {
-            Some(rhs) => *self == rhs,
-            None => false
-        }
+        self.as_real().eqv(&rhs.as_real())
     }
 }
 
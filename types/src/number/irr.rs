@@ -4,19 +4,50 @@
 
 use std::any::Any;
 use std::fmt;
-use number::{Frac, Int, Number};
+use number::{Frac, Int, Number, Real};
 use object::{Obj, Object};
 
+/// An inexact real number.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Irr(pub f64);
 
 impl Irr {
     pub fn zero() -> Irr { Irr(0.0) }
+
+    /// Split a finite float into an odd integer `m` and an exponent `e`, such that the float is
+    /// exactly m·2^e. Zero is 0·2^0.
+    pub(crate) fn parts(&self) -> Option<(i64, i32)> {
+        if !self.0.is_finite() {
+            return None;
+        }
+        if self.0 == 0.0 {
+            return Some((0, 0));
+        }
+        let bits = self.0.to_bits();
+        let exp = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = (bits & 0xf_ffff_ffff_ffff) as i64;
+        // Subnormal floats don't have the implicit leading 1, and have the smallest exponent.
+        let (m, e) = if exp == 0 { (fraction, -1074) } else { (fraction | 1 << 52, exp - 1075) };
+        let zeros = m.trailing_zeros() as i32;
+        let m = m >> zeros;
+        Some((if bits >> 63 == 0 { m } else { -m }, e + zeros))
+    }
 }
 
 impl fmt::Display for Irr {
+    /// Write the number so it reads back as an inexact number, with a decimal point even if it's
+    /// whole.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+        let x = self.0;
+        if x.is_nan() {
+            write!(f, "+nan.0")
+        } else if x.is_infinite() {
+            write!(f, "{}inf.0", if x > 0.0 { "+" } else { "-" })
+        } else if x.fract() == 0.0 {
+            write!(f, "{:.1}", x)
+        } else {
+            write!(f, "{}", x)
+        }
     }
 }
 
@@ -39,15 +70,22 @@ impl Number for Irr {
         }
     }
 
+    /// The exact fraction equal to this number, if there is one that fits.
     fn as_frac(&self) -> Option<Frac> {
-        if !self.0.is_infinite() && !self.0.is_nan() {
-            // TODO
-            None
+        let (m, e) = self.parts()?;
+        if e >= 0 {
+            let p = if e < 63 { m.checked_mul(1 << e)? } else if m == 0 { 0 } else { return None };
+            Frac::from_ints(p, 1)
+        } else if e > -63 {
+            // m is odd, so this is already in lowest terms.
+            Frac::from_ints(m, 1 << -e)
         } else {
             None
         }
     }
 
+    fn as_real(&self) -> Real { Real::Irrational(*self) }
+    fn is_exact(&self) -> bool { false }
     fn is_zero(&self) -> bool { self.0 == 0.0 }
 }
 
@@ -67,6 +105,44 @@ impl PartialEq<Obj> for Irr {
 
 impl<'a> PartialEq<Number + 'a> for Irr {
     fn eq(&self, rhs: &(Number + 'a)) -> bool {
-        false
+        self.as_real().eqv(&rhs.as_real())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn irrationals_are_inexact() {
+        assert!(!Irr(4.0).is_exact());
+    }
+
+    #[test]
+    fn irrationals_as_fractions() {
+        assert_eq!(Irr(3.5).as_frac(), Frac::from_ints(7, 2));
+        assert_eq!(Irr(-0.75).as_frac(), Frac::from_ints(-3, 4));
+        assert_eq!(Irr(1024.0).as_frac(), Frac::from_ints(1024, 1));
+        assert_eq!(Irr(0.1).as_frac(), Frac::from_ints(3602879701896397, 36028797018963968));
+        assert_eq!(Irr(1e30).as_frac(), None);
+        assert_eq!(Irr(1e-30).as_frac(), None);
+        assert_eq!(Irr(f64::NAN).as_frac(), None);
+    }
+
+    #[test]
+    fn irrationals_display_as_inexact() {
+        assert_eq!(Irr(2.5).to_string(), "2.5");
+        assert_eq!(Irr(3.0).to_string(), "3.0");
+        assert_eq!(Irr(-0.0).to_string(), "-0.0");
+        assert_eq!(Irr(f64::INFINITY).to_string(), "+inf.0");
+        assert_eq!(Irr(f64::NEG_INFINITY).to_string(), "-inf.0");
+        assert_eq!(Irr(f64::NAN).to_string(), "+nan.0");
+    }
+
+    #[test]
+    fn irrationals_are_only_equal_to_inexact_numbers() {
+        assert_eq!(Obj::new(Irr(2.0)), Obj::new(Irr(2.0)));
+        assert_ne!(Obj::new(Irr(2.0)), Obj::new(Int(2)));
+        assert_ne!(Obj::new(Int(2)), Obj::new(Irr(2.0)));
     }
 }
@@ -10,6 +10,9 @@
 //! unequivocally cast to the type below it, but the reverse is not necessarily true. So, an
 //! Integer can be cast as a Rational (by putting its value over 1), but a Rational like 1/2 cannot
 //! be represented as an Integer.
+//!
+//! Arithmetic between numbers of different types goes through `Real`, which promotes them to a
+//! common type.
 
 use object::Object;
 
@@ -17,10 +20,12 @@ mod arith;
 mod frac;
 mod integer;
 mod irr;
+mod real;
 
 pub use self::frac::Frac;
 pub use self::integer::Int;
 pub use self::irr::Irr;
+pub use self::real::Real;
 
 pub trait Number: 
     Object 
@@ -29,6 +34,8 @@ pub trait Number:
     fn as_int(&self) -> Option<Int> { None }
     /// Cast this Number to a Frac if possible.
     fn as_frac(&self) -> Option<Frac> { None }
+    /// This Number as a Real, for doing arithmetic with Numbers of other types.
+    fn as_real(&self) -> Real;
     /// Return `true` if this Number is an exact representation of its value.
     fn is_exact(&self) -> bool { true }
     /// Return `true` if this Number is equal to 0.
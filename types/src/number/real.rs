@@ -2,219 +2,384 @@
  * Eryn Wells <eryn@erynwells.me>
  */
 
+//! # Reals
+//!
+//! `Real` is any one of the numbers below complex in the tower, tagged with which it is, so any
+//! two of them can be combined. Arithmetic on two reals promotes the lower of them to the type of
+//! the higher, and works the way R7RS says to:
+//!
+//! - If both are exact, the result is exact, and demoted as far as it goes, so `1/2 + 1/2` is the
+//!   integer 1.
+//! - If either is inexact, the result is inexact. Inexactness is contagious.
+//!
+//! There are no bignums yet, so an exact result that doesn't fit in 64 bits comes out inexact
+//! instead.
+
+use std::cmp::Ordering;
 use std::fmt;
-use super::*;
-use self::math::*;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use number::{Frac, Int, Irr, Number};
+use object::Obj;
 
 #[derive(Clone, Copy, Debug)]
 pub enum Real {
     Integer(Int),
-    Rational(Int, Int),
-    Irrational(Flt)
+    Rational(Frac),
+    Irrational(Irr),
 }
 
 impl Real {
-    /// Reduce a fraction to its lowest denominator. For non-Rational values,
-    /// this method returns an unmodified value.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use sibiltypes::number::Real;
-    ///
-    /// assert_eq!(Real::Integer(12).reduce(), Real::Integer(12));
-    /// assert_eq!(Real::Rational(2, 4).reduce(), Real::Rational(1, 2));
-    /// assert_eq!(Real::Rational(3, 7).reduce(), Real::Rational(3, 7));
-    /// assert_eq!(Real::Irrational(2.4).reduce(), Real::Irrational(2.4));
-    /// ```
-    pub fn reduce(self) -> Real {
-        match self {
-            Real::Rational(p, q) => {
-                let gcd = p.gcd(q);
-                if gcd == 1 {
-                    self
-                }
-                else {
-                    Real::Rational(p / gcd, q / gcd)
-                }
-            },
-            _ => self
+    /// Make an exact number from a fraction, demoted as far as it goes. Returns `None` if the
+    /// denominator is zero, or if the reduced fraction doesn't fit.
+    fn exact(p: i128, q: i128) -> Option<Real> {
+        Frac::reduced(p, q).map(|frac| Real::Rational(frac).demote())
+    }
+
+    /// Return `true` if this number is an exact representation of its value.
+    pub fn is_exact(&self) -> bool {
+        !matches!(*self, Real::Irrational(_))
+    }
+
+    /// Return `true` if this number is equal to 0.
+    pub fn is_zero(&self) -> bool {
+        self.to_f64() == 0.0
+    }
+
+    /// The exact value of this number as a numerator and a positive denominator.
+    fn ratio(&self) -> Option<(i128, i128)> {
+        let frac = match *self {
+            Real::Integer(i) => return Some((i.0 as i128, 1)),
+            Real::Rational(f) => f,
+            Real::Irrational(irr) => irr.as_frac()?,
+        };
+        Some((frac.numerator().0 as i128, frac.denominator().0 as i128))
+    }
+
+    /// The value of this number as a float, which may lose precision.
+    pub fn to_f64(&self) -> f64 {
+        match *self {
+            Real::Integer(i) => i.0 as f64,
+            Real::Rational(f) => f.quotient(),
+            Real::Irrational(irr) => irr.0,
         }
     }
 
     /// Promote a Real to the next highest type.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use sibiltypes::number::Real;
-    /// 
-    /// assert_eq!(Real::Integer(5).promote_once(), Real::Rational(5, 1));
-    /// assert_eq!(Real::Rational(3, 5).promote_once(), Real::Irrational(0.6));
-    /// assert_eq!(Real::Irrational(5.65).promote_once(), Real::Irrational(5.65));
-    /// ```
     pub fn promote_once(self) -> Real {
         match self {
-            Real::Integer(v) => Real::Rational(v, 1),
-            Real::Rational(p, q) => Real::Irrational(p as Flt / q as Flt),
-            Real::Irrational(_) => self
+            Real::Integer(i) => Real::Rational(Frac::from(i)),
+            Real::Rational(f) => Real::Irrational(Irr::from(f)),
+            Real::Irrational(_) => self,
         }
     }
 
-    /// Demote a Real as far down the tower as possible.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use sibiltypes::number::Real;
-    ///
-    /// assert_eq!(Real::Integer(3).demote(), Real::Integer(3));
-    /// assert_eq!(Real::Rational(3, 1).demote(), Real::Integer(3));
-    /// assert_eq!(Real::Irrational(3.2).demote(), Real::Rational(16, 5));
-    /// assert_eq!(Real::Irrational(3.0).demote(), Real::Integer(3));
-    /// ```
+    /// Demote an exact Real as far down the tower as possible. Inexact numbers stay where they
+    /// are, since demoting them would make them exact; use `to_exact` for that.
     pub fn demote(self) -> Real {
-        match self.demote_once() {
-            Some(demoted) => demoted.demote(),
-            None => self,
+        match self {
+            Real::Rational(f) => match f.as_int() {
+                Some(i) => Real::Integer(i),
+                None => self,
+            },
+            _ => self,
         }
     }
 
-    /// Demote a Real to the next lowest type, if possible.
-    ///
-    /// # Examples
-    ///
-    /// Integers can't reduce.
-    ///
-    /// ```
-    /// use sibiltypes::number::Real;
-    /// assert!(Real::Integer(3).demote_once().is_none());
-    /// ```
-    ///
-    /// Rationals can be demoted in certain cases.
-    ///
-    /// ```
-    /// use sibiltypes::number::Real;
-    /// assert_eq!(Real::Rational(4, 1).demote_once(), Some(Real::Integer(4)));
-    /// assert!(Real::Rational(4, 7).demote_once().is_none());
-    /// ```
-    pub fn demote_once(self) -> Option<Real> {
+    /// The inexact number nearest this one. This is Scheme's `inexact`.
+    pub fn to_inexact(self) -> Real {
+        Real::Irrational(Irr(self.to_f64()))
+    }
+
+    /// The exact number equal to this one, or `None` if there isn't one, as for infinities and
+    /// NaN. This is Scheme's `exact`.
+    pub fn to_exact(self) -> Option<Real> {
+        self.ratio().and_then(|(p, q)| Real::exact(p, q))
+    }
+
+    /// Return `true` if the two numbers are the same exactness, and equal. This is Scheme's
+    /// `eqv?` for numbers, where `=` is `==`.
+    pub fn eqv(&self, rhs: &Real) -> bool {
+        match (*self, *rhs) {
+            (Real::Irrational(a), Real::Irrational(b)) => a.0 == b.0,
+            (a, b) => a.is_exact() && b.is_exact() && a == b,
+        }
+    }
+
+    /// Make an object of this number.
+    pub fn into_obj(self) -> Obj {
         match self {
-            Real::Integer(_) => None,
-            Real::Rational(p, q) => if q == 1 {
-                Some(Real::Integer(p))
+            Real::Integer(i) => Obj::new(i),
+            Real::Rational(f) => Obj::new(f),
+            Real::Irrational(irr) => Obj::new(irr),
+        }
+    }
+
+    /// Combine two numbers exactly if they're both exact, and inexactly otherwise, or if the
+    /// exact result doesn't fit.
+    fn combine<E, I>(self, rhs: Real, exact: E, inexact: I) -> Real
+        where E: Fn(i128, i128, i128, i128) -> Option<(i128, i128)>,
+              I: Fn(f64, f64) -> f64
+    {
+        if self.is_exact() && rhs.is_exact() {
+            let ((p, q), (rp, rq)) = (self.ratio().unwrap(), rhs.ratio().unwrap());
+            if let Some(real) = exact(p, q, rp, rq).and_then(|(p, q)| Real::exact(p, q)) {
+                return real;
             }
-            else {
-                None
+        }
+        Real::Irrational(Irr(inexact(self.to_f64(), rhs.to_f64())))
+    }
+}
+
+/// Compare the exact number `p/q`, where `q` is positive, with a float, without rounding either.
+fn cmp_with_float(p: i128, q: i128, x: Irr) -> Option<Ordering> {
+    if x.0.is_nan() {
+        return None;
+    }
+    if x.0.is_infinite() {
+        return Some(if x.0 > 0.0 { Ordering::Less } else { Ordering::Greater });
+    }
+    // x is m·2^e, so compare p·2^-e with m·q when e is negative, and p with m·q·2^e otherwise.
+    let (m, e) = x.parts().unwrap();
+    let lhs = scaled(p, -e);
+    let rhs = scaled(m as i128 * q, e);
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(lhs.cmp(&rhs)),
+        // Whichever side overflowed is bigger than the other, so its sign decides.
+        (None, _) => Some(if p > 0 { Ordering::Greater } else { Ordering::Less }),
+        (_, None) => Some(if m > 0 { Ordering::Less } else { Ordering::Greater }),
+    }
+}
+
+/// `v·2^k` if `k` is positive, and `v` otherwise, or `None` if that overflows.
+fn scaled(v: i128, k: i32) -> Option<i128> {
+    if v == 0 || k <= 0 {
+        Some(v)
+    } else if k < 127 {
+        v.checked_mul(1 << k)
+    } else {
+        None
+    }
+}
+
+impl Add for Real {
+    type Output = Real;
+    fn add(self, rhs: Real) -> Real {
+        self.combine(rhs, |p, q, rp, rq| {
+            let p = p.checked_mul(rq)?.checked_add(rp.checked_mul(q)?)?;
+            Some((p, q.checked_mul(rq)?))
+        }, |a, b| a + b)
+    }
+}
+
+impl Sub for Real {
+    type Output = Real;
+    fn sub(self, rhs: Real) -> Real {
+        self.combine(rhs, |p, q, rp, rq| {
+            let p = p.checked_mul(rq)?.checked_sub(rp.checked_mul(q)?)?;
+            Some((p, q.checked_mul(rq)?))
+        }, |a, b| a - b)
+    }
+}
+
+impl Mul for Real {
+    type Output = Real;
+    fn mul(self, rhs: Real) -> Real {
+        self.combine(rhs, |p, q, rp, rq| Some((p.checked_mul(rp)?, q.checked_mul(rq)?)), |a, b| a * b)
+    }
+}
+
+impl Div for Real {
+    /// Dividing by exact zero is an error, and gives `None`. Dividing by inexact zero gives an
+    /// infinity or NaN, like floats do.
+    type Output = Option<Real>;
+    fn div(self, rhs: Real) -> Option<Real> {
+        if rhs.is_exact() && rhs.is_zero() {
+            return None;
+        }
+        Some(self.combine(rhs, |p, q, rp, rq| Some((p.checked_mul(rq)?, q.checked_mul(rp)?)), |a, b| a / b))
+    }
+}
+
+impl Neg for Real {
+    type Output = Real;
+    fn neg(self) -> Real {
+        match self {
+            Real::Integer(i) => match i.0.checked_neg() {
+                Some(n) => Real::Integer(Int(n)),
+                None => Real::Irrational(Irr(-(i.0 as f64))),
             },
-            Real::Irrational(v) => {
-                let (p, q) = v.to_rational();
-                // No need to reduce here since p/q is already reduced.
-                Some(Real::Rational(p, q))
-            }
+            Real::Rational(f) => Real::exact(-(f.numerator().0 as i128), f.denominator().0 as i128)
+                .unwrap_or_else(|| Real::Irrational(Irr(-f.quotient()))),
+            Real::Irrational(irr) => Real::Irrational(Irr(-irr.0)),
         }
     }
 }
 
 impl PartialEq for Real {
-    fn eq(&self, other: &Real) -> bool {
-        // TODO: Make comparing different variants possible.
-        match (self, other) {
-            (&Real::Integer(v), &Real::Integer(ov)) => v == ov,
-            (&Real::Rational(p, q), &Real::Rational(op, oq)) => p == op && q == oq,
-            (&Real::Irrational(v), &Real::Irrational(ov)) => v == ov,
-            _ => false
+    /// Numbers are equal if they have the same value, whatever their exactness. This is Scheme's
+    /// `=`.
+    fn eq(&self, rhs: &Real) -> bool {
+        self.partial_cmp(rhs) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Real {
+    /// Compare exactly wherever possible, so comparisons are transitive even where converting to
+    /// a float would round. NaN isn't ordered with anything.
+    fn partial_cmp(&self, rhs: &Real) -> Option<Ordering> {
+        match (*self, *rhs) {
+            (Real::Irrational(a), Real::Irrational(b)) => a.0.partial_cmp(&b.0),
+            (Real::Irrational(a), b) => {
+                let (p, q) = b.ratio().unwrap();
+                cmp_with_float(p, q, a).map(Ordering::reverse)
+            },
+            (a, Real::Irrational(b)) => {
+                let (p, q) = a.ratio().unwrap();
+                cmp_with_float(p, q, b)
+            },
+            (a, b) => {
+                let ((p, q), (rp, rq)) = (a.ratio().unwrap(), b.ratio().unwrap());
+                Some((p * rq).cmp(&(rp * q)))
+            },
         }
     }
 }
 
 impl fmt::Display for Real {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // TODO: Figure out how to print the sign if `f.sign_plus() == true`.
         match *self {
-            Real::Integer(v) => write!(f, "{}", v),
-            Real::Rational(p, q) => write!(f, "{}/{}", p, q),
-            Real::Irrational(v) => write!(f, "{}", v),
+            Real::Integer(i) => write!(f, "{}", i),
+            Real::Rational(fr) => write!(f, "{}", fr),
+            Real::Irrational(irr) => write!(f, "{}", irr),
         }
     }
 }
 
+impl From<Int> for Real {
+    fn from(i: Int) -> Real { Real::Integer(i) }
+}
+
+impl From<Frac> for Real {
+    fn from(f: Frac) -> Real { Real::Rational(f) }
+}
+
+impl From<Irr> for Real {
+    fn from(irr: Irr) -> Real { Real::Irrational(irr) }
+}
+
 #[cfg(test)]
 mod tests {
-    mod integers {
-        use number::real::*;
-
-        #[test]
-        fn are_equal() {
-            assert_eq!(Real::Integer(3), Real::Integer(3));
-            assert_ne!(Real::Integer(12), Real::Integer(9));
-        }
+    use super::*;
 
-        #[test]
-        fn reduce_to_themselves() {
-            assert_eq!(Real::Integer(4).reduce(), Real::Integer(4));
-        }
+    fn int(i: i64) -> Real { Real::Integer(Int(i)) }
+    fn frac(p: i64, q: i64) -> Real { Real::Rational(Frac::from_ints(p, q).unwrap()) }
+    fn irr(x: f64) -> Real { Real::Irrational(Irr(x)) }
 
-        #[test]
-        fn promote_to_rationals() {
-            assert_eq!(Real::Integer(6).promote_once(), Real::Rational(6, 1));
-        }
+    /// Check the result is the same exactness and value as expected.
+    fn assert_eqv(result: Real, expected: Real) {
+        assert!(result.eqv(&expected), "expected {}, found {}", expected, result);
+    }
 
-        #[test]
-        fn demote_to_themselves() {
-            assert_eq!(Real::Integer(6).demote(), Real::Integer(6));
-        }
+    #[test]
+    fn promoting() {
+        assert_eqv(int(5).promote_once(), frac(5, 1));
+        assert_eqv(frac(3, 5).promote_once(), irr(0.6));
+        assert_eqv(irr(5.65).promote_once(), irr(5.65));
     }
 
-    mod rationals {
-        use number::real::*;
+    #[test]
+    fn demoting() {
+        assert!(matches!(frac(4, 1).demote(), Real::Integer(Int(4))));
+        assert!(matches!(frac(4, 7).demote(), Real::Rational(_)));
+        assert!(matches!(irr(3.0).demote(), Real::Irrational(_)));
+    }
 
-        #[test]
-        fn are_equal() {
-            assert_eq!(Real::Rational(3, 2), Real::Rational(3, 2));
-            assert_ne!(Real::Rational(12, 4), Real::Rational(9, 7));
-        }
+    #[test]
+    fn exact_arithmetic_stays_exact() {
+        assert_eqv(int(3) + int(5), int(8));
+        assert_eqv(frac(1, 4) + frac(1, 4), frac(1, 2));
+        assert_eqv(frac(4, 7) + frac(14, 3), frac(110, 21));
+        assert_eqv(frac(1, 2) + frac(1, 2), int(1));
+        assert_eqv(int(1) - frac(1, 3), frac(2, 3));
+        assert_eqv(frac(2, 3) * int(3), int(2));
+        assert_eqv((int(1) / int(3)).unwrap(), frac(1, 3));
+        assert_eqv((frac(1, 2) / frac(1, 4)).unwrap(), int(2));
+        assert_eqv((int(-6) / int(4)).unwrap(), frac(-3, 2));
+        assert_eqv((int(6) / int(-4)).unwrap(), frac(-3, 2));
+    }
 
-        #[test]
-        fn reduce_correctly() {
-            assert_eq!(Real::Rational(2, 4).reduce(), Real::Rational(1, 2));
-        }
+    #[test]
+    fn inexactness_is_contagious() {
+        assert_eqv(int(1) + irr(0.5), irr(1.5));
+        assert_eqv(frac(1, 2) + irr(0.25), irr(0.75));
+        assert_eqv(irr(2.0) * int(0), irr(0.0));
+        assert_eqv(int(1) - irr(1.0), irr(0.0));
+        assert_eqv((irr(3.0) / frac(3, 2)).unwrap(), irr(2.0));
+    }
 
-        #[test]
-        fn promote_to_irrationals() {
-            assert_eq!(Real::Rational(3, 2).promote_once(), Real::Irrational(1.5));
-        }
+    #[test]
+    fn dividing_by_zero() {
+        assert!((int(1) / int(0)).is_none());
+        assert!((irr(1.0) / int(0)).is_none());
+        assert_eqv((int(1) / irr(0.0)).unwrap(), irr(f64::INFINITY));
+    }
 
-        #[test]
-        fn demote_to_integers_if_possible() {
-            assert_eq!(Real::Rational(3, 2).demote(), Real::Rational(3, 2));
-            assert_eq!(Real::Rational(4, 1).demote(), Real::Integer(4));
-        }
+    #[test]
+    fn overflow_becomes_inexact() {
+        let big = int(i64::MAX);
+        assert!(!(big + int(1)).is_exact());
+        assert!(!(big * big).is_exact());
+        assert!(!(-int(i64::MIN)).is_exact());
+        assert_eqv(big + int(-1), int(i64::MAX - 1));
     }
 
-    mod irrationals {
-        use number::real::*;
+    #[test]
+    fn dividing_the_smallest_int() {
+        assert_eqv((int(i64::MIN) / int(5)).unwrap(), frac(i64::MIN, 5));
+        assert_eqv((int(i64::MIN) / int(-1)).unwrap(), irr(-(i64::MIN as f64)));
+        assert_eqv((int(i64::MIN) / int(2)).unwrap(), int(i64::MIN / 2));
+    }
 
-        #[test]
-        fn are_equal() {
-            assert_eq!(Real::Irrational(3.2), Real::Irrational(3.2));
-            assert_ne!(Real::Irrational(12.0), Real::Irrational(9.0));
-        }
+    #[test]
+    fn comparing_across_types() {
+        assert_eq!(int(2), frac(4, 2));
+        assert_eq!(int(2), irr(2.0));
+        assert_eq!(frac(1, 2), irr(0.5));
+        assert!(frac(1, 3) < frac(1, 2));
+        assert!(int(1) > frac(2, 3));
+        assert!(irr(0.3) < frac(1, 3));
+        assert!(irr(f64::INFINITY) > int(i64::MAX));
+        assert_eq!(irr(f64::NAN).partial_cmp(&int(0)), None);
+    }
 
-        #[test]
-        fn reduce_to_themselves() {
-            assert_eq!(Real::Irrational(3.2).reduce(), Real::Irrational(3.2));
-        }
+    #[test]
+    fn comparing_is_exact() {
+        // These two are different numbers, but the same float.
+        let big = int(i64::MAX);
+        let big_float = irr(i64::MAX as f64);
+        assert!(big < big_float);
+        assert!(big != big_float);
+        assert!(big_float > big);
+        assert_eq!(int(i64::MIN), irr(i64::MIN as f64));
+        assert!(irr(1e-30) > int(0));
+        assert!(irr(1e-30) < frac(1, i64::MAX));
+        assert!(irr(-1e30) < int(i64::MIN));
+    }
 
-        #[test]
-        fn promote_to_themselves() {
-            assert_eq!(Real::Irrational(3.2).promote_once(), Real::Irrational(3.2));
-        }
+    #[test]
+    fn eqv_cares_about_exactness() {
+        assert!(int(2).eqv(&frac(2, 1)));
+        assert!(!int(2).eqv(&irr(2.0)));
+        assert!(irr(0.5).eqv(&irr(0.5)));
+    }
 
-        #[test]
-        fn demote_to_rationals() {
-            assert_eq!(Real::Irrational(3.2).demote(), Real::Rational(16, 5));
-            assert_eq!(Real::Irrational(3.5).demote(), Real::Rational(7, 2));
-        }
+    #[test]
+    fn exactness_conversions() {
+        assert_eqv(int(3).to_inexact(), irr(3.0));
+        assert_eqv(irr(3.0).to_exact().unwrap(), int(3));
+        assert_eqv(irr(3.5).to_exact().unwrap(), frac(7, 2));
+        assert_eqv(irr(-0.125).to_exact().unwrap(), frac(-1, 8));
+        assert!(irr(f64::NAN).to_exact().is_none());
+        assert!(irr(f64::INFINITY).to_exact().is_none());
     }
 }
@@ -5,7 +5,15 @@
 //! # Objects
 //!
 //! All Scheme types implement the `Object` trait defined in this module. Most
-//! references to objects are going to be through an `ObjectPtr`.
+//! references to objects are going to be through an `Obj`.
+//!
+//! ## Sharing
+//!
+//! Cloning an `Obj` makes another reference to the same object, the way Scheme
+//! variables and data structures refer to objects. Pairs and vectors can be
+//! changed in place, so structures can share parts and even refer back to
+//! themselves. Objects are reference counted, and the `heap` module collects
+//! the cycles that reference counting can't free.
 //!
 //! ## Type Predicates
 //!
@@ -13,17 +21,22 @@
 //! available types in Scheme. These predicates are implemented as `is_*`
 //! methods in a bunch of `Is*` traits defined below.
 
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::ops::Deref;
 use std::mem;
 use std::any::Any;
 use std::fmt;
+use std::rc::Rc;
 use super::*;
+use heap;
 use number::Number;
+use printer::Printer;
 
-#[derive(Debug)]
+#[derive(Clone)]
 pub enum Obj {
     Null,
-    Ptr(Box<Object>)
+    Ptr(Rc<Object>)
 }
 
 pub trait Object:
@@ -35,17 +48,57 @@ pub trait Object:
     fn as_any(&self) -> &Any;
     /// Cast this Object to a Bool if possible.
     fn as_bool(&self) -> Option<&Bool> { None }
+    /// Cast this Object to a Char if possible.
+    fn as_char(&self) -> Option<&Char> { None }
     /// Cast this Object to a Pair if possible.
     fn as_pair(&self) -> Option<&Pair> { None }
+    /// Cast this Object to a Str if possible.
+    fn as_str(&self) -> Option<&Str> { None }
     /// Cast this Object to a Sym if possible.
     fn as_sym(&self) -> Option<&Sym> { None }
     /// Cast this Object to a Number if possible.
     fn as_num(&self) -> Option<&Number> { None }
+    /// Cast this Object to a Vector if possible.
+    fn as_vector(&self) -> Option<&Vector> { None }
+    /// Cast this Object to a ByteVector if possible.
+    fn as_bytevector(&self) -> Option<&ByteVector> { None }
+
+    /// Call `visit` with each object this one refers to. Objects that refer to other objects
+    /// have to implement this, or the garbage collector will free objects they refer to.
+    fn trace(&self, _visit: &mut FnMut(&Obj)) { }
+    /// Drop this object's references to other objects. The garbage collector calls this on
+    /// garbage to break the cycles keeping it alive.
+    fn unlink(&self) { }
 }
 
 impl Obj {
     pub fn new<T: Object + 'static>(obj: T) -> Obj {
-        Obj::Ptr(Box::new(obj))
+        let obj: Rc<Object> = Rc::new(obj);
+        heap::register(&obj);
+        Obj::Ptr(obj)
+    }
+
+    /// True if `self` and `other` are Scheme's `eq?`: the same object, or symbols with the same
+    /// name.
+    pub fn is_eq(&self, other: &Obj) -> bool {
+        match (self.obj().and_then(Object::as_sym), other.obj().and_then(Object::as_sym)) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.ptr_eq(other),
+        }
+    }
+
+    /// True if `self` and `other` are the same object. Every empty list is the same object.
+    pub fn ptr_eq(&self, other: &Obj) -> bool {
+        match (self.addr(), other.addr()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// The address of the object, which identifies it for as long as it's alive.
+    pub(crate) fn addr(&self) -> Option<usize> {
+        self.obj().map(addr_of)
     }
 
     pub fn obj<'s, 'r: 's>(&'s self) -> Option<&'r (Object + 's)> {
@@ -75,24 +128,67 @@ impl Obj {
     }
 }
 
+/// The address of `obj`, which identifies it for as long as it's alive.
+pub(crate) fn addr_of(obj: &Object) -> usize {
+    obj as *const Object as *const () as usize
+}
+
 impl fmt::Display for Obj {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Obj::Null => write!(f, "()"),
-            Obj::Ptr(obj) => write!(f, "{}", obj)
+            Obj::Ptr(obj) => Printer::new(obj.deref()).print(obj.deref(), f)
         }
     }
 }
 
-impl PartialEq for Obj {
-    fn eq(&self, rhs: &Self) -> bool {
+impl fmt::Debug for Obj {
+    /// Objects are written out the way `Display` writes them, so cyclic structures don't go on
+    /// forever.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Obj::Null => rhs.is_null(),
-            Obj::Ptr(ref inner) => inner.deref() == rhs
+            Obj::Null => write!(f, "Null"),
+            Obj::Ptr(_) => write!(f, "Ptr({})", self)
         }
     }
 }
 
+thread_local! {
+    /// The pairs of objects an equality test in progress is assuming are equal, and how deeply
+    /// nested the test is. Comparing a pair of objects a second time means the test has gone
+    /// around a cycle in both of them, so as far as this pair is concerned they're equal.
+    static ASSUMED_EQUAL: RefCell<(usize, HashSet<(usize, usize)>)> = RefCell::new((0, HashSet::new()));
+}
+
+impl PartialEq for Obj {
+    /// Structural equality, like Scheme's `equal?`. This works on cyclic structures too.
+    fn eq(&self, rhs: &Self) -> bool {
+        let inner = match self {
+            Obj::Null => return rhs.is_null(),
+            Obj::Ptr(ref inner) => inner,
+        };
+        let key = match rhs.addr() {
+            Some(rhs_addr) if rhs_addr == addr_of(inner.deref()) => return true,
+            Some(rhs_addr) => (addr_of(inner.deref()), rhs_addr),
+            None => return false,
+        };
+        let seen = ASSUMED_EQUAL.with(|a| {
+            let mut a = a.borrow_mut();
+            a.0 += 1;
+            !a.1.insert(key)
+        });
+        let eq = seen || inner.deref() == rhs;
+        ASSUMED_EQUAL.with(|a| {
+            let mut a = a.borrow_mut();
+            a.0 -= 1;
+            if a.0 == 0 {
+                a.1.clear();
+            }
+        });
+        eq
+    }
+}
+
 //#[derive(Debug, PartialEq)]
 //pub enum Object {
 //    ByteVector(Vec<u8>),
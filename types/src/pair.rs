@@ -3,67 +3,65 @@
  */
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::fmt;
 use object::{Obj, Object};
+use printer::Printer;
 
+/// A pair of objects. Either half can be changed in place, which is how lists end up sharing
+/// structure or referring back to themselves.
 #[derive(Debug, PartialEq)]
 pub struct Pair {
-    pub car: Obj,
-    pub cdr: Obj,
+    car: RefCell<Obj>,
+    cdr: RefCell<Obj>,
 }
 
 impl Pair {
     pub fn new(car: Obj, cdr: Obj) -> Pair {
-        Pair { car, cdr }
+        Pair { car: RefCell::new(car), cdr: RefCell::new(cdr) }
     }
 
     pub fn empty() -> Pair {
-        Pair { car: Obj::Null, cdr: Obj::Null }
+        Pair::new(Obj::Null, Obj::Null)
     }
 
     pub fn with_car(car: Obj) -> Pair {
-        Pair { car: car, cdr: Obj::Null }
+        Pair::new(car, Obj::Null)
     }
 
-    fn fmt_pair(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let r = write!(f, "{}", self.car);
-        r.and_then(|r| match self.cdr {
-            Obj::Null => Ok(r),  // Don't write anything.
-            Obj::Ptr(ref next) => {
-                match next.as_pair() {
-                    Some(next_pair) => write!(f, " ").and_then(|_| next_pair.fmt_pair(f)),
-                    None => write!(f, " . {}", next)
-                }
-            }
-        })
-    }
+    pub fn car(&self) -> Obj { self.car.borrow().clone() }
+    pub fn cdr(&self) -> Obj { self.cdr.borrow().clone() }
+
+    pub fn set_car(&self, obj: Obj) { *self.car.borrow_mut() = obj; }
+    pub fn set_cdr(&self, obj: Obj) { *self.cdr.borrow_mut() = obj; }
 }
 
 impl Object for Pair {
     fn as_any(&self) -> &Any { self }
     fn as_pair(&self) -> Option<&Pair> { Some(self) }
+
+    fn trace(&self, visit: &mut FnMut(&Obj)) {
+        visit(&self.car.borrow());
+        visit(&self.cdr.borrow());
+    }
+
+    fn unlink(&self) {
+        self.set_car(Obj::Null);
+        self.set_cdr(Obj::Null);
+    }
 }
 
 impl fmt::Display for Pair {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "(").and_then(|_| self.fmt_pair(f))
-                      .and_then(|_| write!(f, ")"))
+        Printer::new(self).print(self, f)
     }
 }
 
 impl PartialEq<Obj> for Pair {
     fn eq(&self, rhs: &Obj) -> bool {
-        match rhs {
-            Obj::Null => false,
-            Obj::Ptr(ref rhs) => {
-                if let Some(rhs_pair) = rhs.as_pair() {
-                    let car_eq = self.car == rhs_pair.car;
-                    let cdr_eq = self.cdr == rhs_pair.cdr;
-                    car_eq && cdr_eq
-                } else {
-                    false
-                }
-            }
+        match rhs.obj().and_then(Object::as_pair) {
+            Some(rhs_pair) => self.car() == rhs_pair.car() && self.cdr() == rhs_pair.cdr(),
+            None => false
         }
     }
 }
@@ -71,7 +69,7 @@ impl PartialEq<Obj> for Pair {
 #[cfg(test)]
 mod tests {
     use super::Pair;
-    use object::Obj;
+    use object::{Obj, Object};
     use sym::Sym;
 
     #[test]
@@ -113,4 +111,41 @@ mod tests {
         let disp = format!("{}", p);
         assert_eq!(disp, "(abc def . ghi)");
     }
+
+    #[test]
+    fn pairs_can_share_a_tail() {
+        let tail = Obj::new(Pair::with_car(Obj::new(Sym::with_str("c"))));
+        let a = Obj::new(Pair::new(Obj::new(Sym::with_str("a")), tail.clone()));
+        let b = Obj::new(Pair::new(Obj::new(Sym::with_str("b")), tail.clone()));
+        let a_tail = a.obj().and_then(Object::as_pair).unwrap().cdr();
+        let b_tail = b.obj().and_then(Object::as_pair).unwrap().cdr();
+        assert!(a_tail.ptr_eq(&b_tail));
+        assert_eq!(format!("{} {}", a, b), "(a c) (b c)");
+    }
+
+    #[test]
+    fn display_cycles_with_labels() {
+        let list = Obj::new(Pair::with_car(Obj::new(Sym::with_str("b"))));
+        let head = Obj::new(Pair::new(Obj::new(Sym::with_str("a")), list.clone()));
+        list.obj().and_then(Object::as_pair).unwrap().set_cdr(head.clone());
+        assert_eq!(format!("{}", head), "#0=(a b . #0#)");
+        assert_eq!(format!("{}", list), "#0=(b a . #0#)");
+
+        let head = Obj::new(Pair::with_car(Obj::Null));
+        head.obj().and_then(Object::as_pair).unwrap().set_car(head.clone());
+        assert_eq!(format!("{}", head), "#0=(#0#)");
+    }
+
+    #[test]
+    fn eq_cycles() {
+        let cycle = || {
+            let head = Obj::new(Pair::with_car(Obj::new(Sym::with_str("a"))));
+            head.obj().and_then(Object::as_pair).unwrap().set_cdr(head.clone());
+            head
+        };
+        let (a, b) = (cycle(), cycle());
+        assert!(!a.ptr_eq(&b));
+        assert_eq!(a, b);
+        assert_ne!(a, Obj::new(Pair::with_car(Obj::new(Sym::with_str("a")))));
+    }
 }
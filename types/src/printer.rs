@@ -0,0 +1,163 @@
+/* types/src/printer.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! Writing out pairs and vectors, which might refer back to themselves. Objects that are part of
+//! a cycle get a datum label, like `#0=(a b . #0#)`, so printing them stops.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use object::{addr_of, Obj, Object};
+use pair::Pair;
+
+pub struct Printer {
+    /// Objects reachable from themselves.
+    cyclic: HashSet<usize>,
+    /// Objects that have been visited while looking for cycles.
+    seen: HashSet<usize>,
+    /// Objects on the path currently being visited.
+    on_stack: HashSet<usize>,
+    /// The labels given to cyclic objects that have been printed.
+    labels: HashMap<usize, usize>,
+}
+
+impl Printer {
+    /// Create a Printer for `root` and everything reachable from it.
+    pub fn new(root: &Object) -> Printer {
+        let mut printer = Printer {
+            cyclic: HashSet::new(),
+            seen: HashSet::new(),
+            on_stack: HashSet::new(),
+            labels: HashMap::new(),
+        };
+        printer.visit(root);
+        printer
+    }
+
+    pub fn print(&mut self, obj: &Object, f: &mut fmt::Formatter) -> fmt::Result {
+        let addr = addr_of(obj);
+        if self.cyclic.contains(&addr) {
+            if let Some(label) = self.labels.get(&addr) {
+                return write!(f, "#{}#", label);
+            }
+            let label = self.labels.len();
+            self.labels.insert(addr, label);
+            write!(f, "#{}=", label)?;
+        }
+        if let Some(pair) = obj.as_pair() {
+            self.print_list(pair, f)
+        } else if let Some(vector) = obj.as_vector() {
+            write!(f, "#(")?;
+            for (i, item) in vector.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                self.print_obj(&item, f)?;
+            }
+            write!(f, ")")
+        } else {
+            write!(f, "{}", obj)
+        }
+    }
+
+    fn print_obj(&mut self, obj: &Obj, f: &mut fmt::Formatter) -> fmt::Result {
+        match obj.obj() {
+            Some(obj) => self.print(obj, f),
+            None => write!(f, "()"),
+        }
+    }
+
+    fn print_list(&mut self, pair: &Pair, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        self.print_obj(&pair.car(), f)?;
+        let mut tail = pair.cdr();
+        loop {
+            let next = {
+                let obj = match tail.obj() {
+                    Some(obj) => obj,
+                    None => break,
+                };
+                match obj.as_pair() {
+                    // A labeled pair has to be written with its label, so it can't continue the
+                    // list.
+                    Some(pair) if !self.cyclic.contains(&addr_of(obj)) => {
+                        write!(f, " ")?;
+                        self.print_obj(&pair.car(), f)?;
+                        pair.cdr()
+                    },
+                    _ => {
+                        write!(f, " . ")?;
+                        self.print(obj, f)?;
+                        break;
+                    },
+                }
+            };
+            tail = next;
+        }
+        write!(f, ")")
+    }
+
+    /// Walk the objects reachable from `obj`, noting the ones that are reachable from themselves.
+    fn visit(&mut self, obj: &Object) {
+        let addr = addr_of(obj);
+        if self.on_stack.contains(&addr) {
+            self.cyclic.insert(addr);
+            return;
+        }
+        if !self.seen.insert(addr) {
+            return;
+        }
+        self.on_stack.insert(addr);
+        if let Some(vector) = obj.as_vector() {
+            for item in vector.iter() {
+                if let Some(item) = item.obj() {
+                    self.visit(item);
+                }
+            }
+        } else if let Some(pair) = obj.as_pair() {
+            if let Some(car) = pair.car().obj() {
+                self.visit(car);
+            }
+            self.visit_tail(pair.cdr());
+        }
+        self.on_stack.remove(&addr);
+    }
+
+    /// Walk the rest of a list. This is a loop so long lists don't run out of stack.
+    fn visit_tail(&mut self, mut tail: Obj) {
+        let mut pushed = Vec::new();
+        loop {
+            let next = {
+                let obj = match tail.obj() {
+                    Some(obj) => obj,
+                    None => break,
+                };
+                let pair = match obj.as_pair() {
+                    Some(pair) => pair,
+                    None => {
+                        self.visit(obj);
+                        break;
+                    },
+                };
+                let addr = addr_of(obj);
+                if self.on_stack.contains(&addr) {
+                    self.cyclic.insert(addr);
+                    break;
+                }
+                if !self.seen.insert(addr) {
+                    break;
+                }
+                self.on_stack.insert(addr);
+                pushed.push(addr);
+                if let Some(car) = pair.car().obj() {
+                    self.visit(car);
+                }
+                pair.cdr()
+            };
+            tail = next;
+        }
+        for addr in pushed {
+            self.on_stack.remove(&addr);
+        }
+    }
+}
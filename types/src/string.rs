@@ -0,0 +1,292 @@
+/* types/src/string.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+//! # Strings
+//!
+//! Scheme strings are mutable sequences of characters, indexed by character. A string that's all
+//! ASCII is stored a byte per character; the first character outside ASCII widens it to a `char`
+//! per character. Either way, getting or setting a character takes constant time.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use object::{Obj, Object};
+
+/// A mutable Scheme string.
+#[derive(Debug, PartialEq)]
+pub struct Str(RefCell<Text>);
+
+#[derive(Clone, Debug)]
+enum Text {
+    Ascii(Vec<u8>),
+    Wide(Vec<char>),
+}
+
+impl Text {
+    fn len(&self) -> usize {
+        match *self {
+            Text::Ascii(ref bytes) => bytes.len(),
+            Text::Wide(ref chars) => chars.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<char> {
+        match *self {
+            Text::Ascii(ref bytes) => bytes.get(index).map(|&b| b as char),
+            Text::Wide(ref chars) => chars.get(index).cloned(),
+        }
+    }
+
+    /// The characters from `start` up to `end`.
+    fn chars(&self, start: usize, end: usize) -> Vec<char> {
+        (start..end).filter_map(|i| self.get(i)).collect()
+    }
+
+    /// Make room for `c`, if it's not ASCII.
+    fn widen_for(&mut self, c: char) {
+        if c.is_ascii() {
+            return;
+        }
+        if let Text::Ascii(ref bytes) = *self {
+            let chars = bytes.iter().map(|&b| b as char).collect();
+            *self = Text::Wide(chars);
+        }
+    }
+
+    /// Replace the character at `index`, which has to be in range.
+    fn set(&mut self, index: usize, c: char) {
+        self.widen_for(c);
+        match *self {
+            Text::Ascii(ref mut bytes) => bytes[index] = c as u8,
+            Text::Wide(ref mut chars) => chars[index] = c,
+        }
+    }
+}
+
+impl PartialEq for Text {
+    fn eq(&self, rhs: &Text) -> bool {
+        match (self, rhs) {
+            (Text::Ascii(a), Text::Ascii(b)) => a == b,
+            _ => self.len() == rhs.len() && (0..self.len()).all(|i| self.get(i) == rhs.get(i)),
+        }
+    }
+}
+
+impl Str {
+    pub fn new(value: String) -> Str {
+        Str::with_str(&value)
+    }
+
+    pub fn with_str(value: &str) -> Str {
+        let text = if value.is_ascii() {
+            Text::Ascii(value.as_bytes().to_vec())
+        } else {
+            Text::Wide(value.chars().collect())
+        };
+        Str(RefCell::new(text))
+    }
+
+    /// A string of `len` copies of `c`. This is Scheme's `make-string`.
+    pub fn filled(len: usize, c: char) -> Str {
+        let text = if c.is_ascii() {
+            Text::Ascii(vec![c as u8; len])
+        } else {
+            Text::Wide(vec![c; len])
+        };
+        Str(RefCell::new(text))
+    }
+
+    /// The number of characters in the string.
+    pub fn len(&self) -> usize { self.0.borrow().len() }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// The character at `index`. This is Scheme's `string-ref`.
+    pub fn get(&self, index: usize) -> Option<char> {
+        self.0.borrow().get(index)
+    }
+
+    /// Replace the character at `index`. Returns false if `index` is out of range. This is
+    /// Scheme's `string-set!`.
+    pub fn set(&self, index: usize, c: char) -> bool {
+        let mut text = self.0.borrow_mut();
+        if index >= text.len() {
+            return false;
+        }
+        text.set(index, c);
+        true
+    }
+
+    /// A new string of the characters from `start` up to `end`, or `None` if that isn't a range
+    /// of this string. This is Scheme's `substring` and `string-copy`.
+    pub fn substring(&self, start: usize, end: usize) -> Option<Str> {
+        if !self.is_range(start, end) {
+            return None;
+        }
+        let text = match *self.0.borrow() {
+            Text::Ascii(ref bytes) => Text::Ascii(bytes[start..end].to_vec()),
+            Text::Wide(ref chars) => Text::Wide(chars[start..end].to_vec()),
+        };
+        Some(Str(RefCell::new(text)))
+    }
+
+    /// Copy the characters of `from` from `start` up to `end` into this string, starting at
+    /// `at`. `from` can be this string, and the ranges can overlap. Returns false, without
+    /// copying anything, if either range is out of bounds. This is Scheme's `string-copy!`.
+    pub fn copy_from(&self, at: usize, from: &Str, start: usize, end: usize) -> bool {
+        if !from.is_range(start, end) || !self.is_range(at, at + (end - start)) {
+            return false;
+        }
+        let chars = from.0.borrow().chars(start, end);
+        let mut text = self.0.borrow_mut();
+        for (i, c) in chars.into_iter().enumerate() {
+            text.set(at + i, c);
+        }
+        true
+    }
+
+    /// Set the characters from `start` up to `end` to `c`. Returns false if that isn't a range of
+    /// this string. This is Scheme's `string-fill!`.
+    pub fn fill(&self, c: char, start: usize, end: usize) -> bool {
+        if !self.is_range(start, end) {
+            return false;
+        }
+        let mut text = self.0.borrow_mut();
+        for i in start..end {
+            text.set(i, c);
+        }
+        true
+    }
+
+    /// The characters of the string, as a Rust string.
+    pub fn value(&self) -> String {
+        let text = self.0.borrow();
+        text.chars(0, text.len()).into_iter().collect()
+    }
+
+    fn is_range(&self, start: usize, end: usize) -> bool {
+        start <= end && end <= self.len()
+    }
+}
+
+impl Object for Str {
+    fn as_any(&self) -> &Any { self }
+    fn as_str(&self) -> Option<&Str> { Some(self) }
+}
+
+impl fmt::Display for Str {
+    /// Write the string so it reads back as the same string, with escapes for quotes,
+    /// backslashes, and control characters.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"")?;
+        let text = self.0.borrow();
+        for i in 0..text.len() {
+            match text.get(i).unwrap() {
+                '"' => write!(f, "\\\"")?,
+                '\\' => write!(f, "\\\\")?,
+                '\x07' => write!(f, "\\a")?,
+                '\x08' => write!(f, "\\b")?,
+                '\t' => write!(f, "\\t")?,
+                '\n' => write!(f, "\\n")?,
+                '\r' => write!(f, "\\r")?,
+                c if c.is_control() => write!(f, "\\x{:x};", c as u32)?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+impl PartialEq<Obj> for Str {
+    fn eq(&self, rhs: &Obj) -> bool {
+        match rhs.obj().and_then(Object::as_str) {
+            Some(rhs_str) => self == rhs_str,
+            None => false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_are_indexed_by_character() {
+        let s = Str::with_str("aλb");
+        assert_eq!(s.len(), 3);
+        assert_eq!(s.get(1), Some('λ'));
+        assert_eq!(s.get(2), Some('b'));
+        assert_eq!(s.get(3), None);
+    }
+
+    #[test]
+    fn setting_characters() {
+        let s = Str::with_str("abc");
+        assert!(s.set(1, 'x'));
+        assert_eq!(s.value(), "axc");
+        assert!(s.set(2, 'λ'));
+        assert_eq!(s.value(), "axλ");
+        assert!(s.set(0, 'z'));
+        assert_eq!(s.value(), "zxλ");
+        assert!(!s.set(3, 'y'));
+    }
+
+    #[test]
+    fn ascii_and_wide_strings_are_equal() {
+        let s = Str::with_str("aλ");
+        s.set(1, 'b');
+        assert_eq!(s, Str::with_str("ab"));
+        assert_ne!(s, Str::with_str("abc"));
+    }
+
+    #[test]
+    fn substrings() {
+        let s = Str::with_str("hello, wörld");
+        assert_eq!(s.substring(7, 12).map(|s| s.value()), Some("wörld".to_string()));
+        assert_eq!(s.substring(0, 0).map(|s| s.value()), Some("".to_string()));
+        assert!(s.substring(5, 4).is_none());
+        assert!(s.substring(0, 13).is_none());
+    }
+
+    #[test]
+    fn copying_between_strings() {
+        let to = Str::with_str("abcde");
+        assert!(to.copy_from(1, &Str::with_str("λμν"), 0, 2));
+        assert_eq!(to.value(), "aλμde");
+        assert!(!to.copy_from(4, &Str::with_str("xyz"), 0, 2));
+        assert_eq!(to.value(), "aλμde");
+    }
+
+    #[test]
+    fn copying_within_a_string() {
+        let s = Str::with_str("abcde");
+        assert!(s.copy_from(1, &s, 0, 3));
+        assert_eq!(s.value(), "aabce");
+        assert!(s.copy_from(0, &s, 2, 5));
+        assert_eq!(s.value(), "bcece");
+    }
+
+    #[test]
+    fn filling() {
+        let s = Str::filled(3, 'a');
+        assert_eq!(s.value(), "aaa");
+        assert!(s.fill('λ', 1, 3));
+        assert_eq!(s.value(), "aλλ");
+        assert!(!s.fill('b', 2, 4));
+    }
+
+    #[test]
+    fn display_escapes() {
+        assert_eq!(Str::with_str("abc").to_string(), "\"abc\"");
+        assert_eq!(Str::with_str("say \"hi\"\\").to_string(), "\"say \\\"hi\\\"\\\\\"");
+        assert_eq!(Str::with_str("a\tb\nc\x01\x7f").to_string(), "\"a\\tb\\nc\\x1;\\x7f;\"");
+        assert_eq!(Str::with_str("λ").to_string(), "\"λ\"");
+    }
+
+    #[test]
+    fn strings_are_objects() {
+        let s = Obj::new(Str::with_str("abc"));
+        assert_eq!(s.obj().and_then(Object::as_str).map(Str::value), Some("abc".to_string()));
+        assert_eq!(s, Obj::new(Str::with_str("abc")));
+    }
+}
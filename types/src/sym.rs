@@ -1,25 +1,84 @@
-/* types/src/symbol.rs
+/* types/src/sym.rs
  * Eryn Wells <eryn@erynwells.me>
  */
 
+//! # Symbols
+//!
+//! Symbols are interned: every symbol with a given name shares one copy of the name, so comparing
+//! symbols is comparing pointers. The table of names only holds weak references, so a name is
+//! freed once no symbol uses it.
+
 use std::any::Any;
-use std::ops::Deref;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::{Rc, Weak};
 use object::Object;
 use super::*;
 
-#[derive(Debug, PartialEq)]
-pub struct Sym(String);
+/// Prune the table of names no earlier than when it has this many.
+const FIRST_PRUNE: usize = 256;
+
+#[derive(Clone, Debug)]
+pub struct Sym(Rc<str>);
+
+struct Names {
+    names: HashMap<String, Weak<str>>,
+    /// Clear out names that aren't in use when the table grows to this size.
+    prune_at: usize,
+}
+
+thread_local! {
+    static NAMES: RefCell<Names> = RefCell::new(Names { names: HashMap::new(), prune_at: FIRST_PRUNE });
+}
 
 impl Sym {
-    /// Creates a Sym with the given String.
+    /// Creates a Sym with the given String. This is Scheme's `string->symbol`.
     pub fn new(value: String) -> Sym {
-        Sym(value)
+        Sym::with_str(&value)
     }
 
-    /// Makes a copy of the input `&str` and creates a Sym with it.
+    /// Creates a Sym named by `value`.
     pub fn with_str(value: &str) -> Sym {
-        Sym(value.to_string())
+        NAMES.with(|names| {
+            let mut names = names.borrow_mut();
+            if let Some(name) = names.names.get(value).and_then(Weak::upgrade) {
+                return Sym(name);
+            }
+            if names.names.len() >= names.prune_at {
+                names.names.retain(|_, name| name.upgrade().is_some());
+                names.prune_at = FIRST_PRUNE.max(2 * names.names.len());
+            }
+            let name: Rc<str> = Rc::from(value);
+            names.names.insert(value.to_string(), Rc::downgrade(&name));
+            Sym(name)
+        })
+    }
+
+    /// The name of the symbol. This is Scheme's `symbol->string`.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    /// A number that identifies this symbol. Symbols with the same name have the same id, for as
+    /// long as any of them is alive.
+    pub fn id(&self) -> usize {
+        self.0.as_ptr() as usize
+    }
+}
+
+impl PartialEq for Sym {
+    fn eq(&self, rhs: &Sym) -> bool {
+        self.id() == rhs.id()
+    }
+}
+
+impl Eq for Sym { }
+
+impl Hash for Sym {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
     }
 }
 
@@ -29,29 +88,42 @@ impl Object for Sym {
 }
 
 impl fmt::Display for Sym {
+    /// Write the symbol so it reads back as the same symbol. Names that aren't plain identifiers
+    /// are written between `|` quotes, with escapes for `|`, `\\`, and control characters.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+        if char::ident::is_plain(self.name()) {
+            return write!(f, "{}", self.0);
+        }
+        write!(f, "|")?;
+        for c in self.name().chars() {
+            match c {
+                '|' => write!(f, "\\|")?,
+                '\\' => write!(f, "\\\\")?,
+                '\x07' => write!(f, "\\a")?,
+                '\x08' => write!(f, "\\b")?,
+                '\t' => write!(f, "\\t")?,
+                '\n' => write!(f, "\\n")?,
+                '\r' => write!(f, "\\r")?,
+                c if c.is_control() => write!(f, "\\x{:x};", c as u32)?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        write!(f, "|")
     }
 }
 
 impl PartialEq<Obj> for Sym {
     fn eq(&self, rhs: &Obj) -> bool {
-        match rhs {
-            Obj::Null => false,
-            Obj::Ptr(ref inner) => {
-                if let Some(rhs_sym) = inner.deref().as_sym() {
-                    self.0 == rhs_sym.0
-                } else {
-                    false
-                }
-            }
+        match rhs.obj().and_then(Object::as_sym) {
+            Some(rhs_sym) => self == rhs_sym,
+            None => false
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Sym;
+    use super::*;
 
     #[test]
     fn eq_syms_with_same_name() {
@@ -59,4 +131,51 @@ mod tests {
         let b = Sym::with_str("abc");
         assert_eq!(a, b);
     }
+
+    #[test]
+    fn syms_with_the_same_name_share_it() {
+        let a = Sym::with_str("abc");
+        let b = Sym::new("abc".to_string());
+        assert_eq!(a.id(), b.id());
+        assert!(Sym::with_str("abd") != a);
+        assert_eq!(b.name(), "abc");
+    }
+
+    #[test]
+    fn syms_with_the_same_name_are_eq() {
+        let a = Obj::new(Sym::with_str("abc"));
+        let b = Obj::new(Sym::with_str("abc"));
+        assert!(a.is_eq(&b));
+        assert!(!a.ptr_eq(&b));
+        assert!(!a.is_eq(&Obj::new(Sym::with_str("abd"))));
+    }
+
+    #[test]
+    fn unused_names_are_freed() {
+        Sym::with_str("only-once");
+        for i in 0..(2 * FIRST_PRUNE) {
+            Sym::new(format!("sym-{}", i));
+        }
+        NAMES.with(|names| {
+            let names = names.borrow();
+            assert!(names.names.len() < 2 * FIRST_PRUNE);
+            assert!(names.names.get("only-once").and_then(Weak::upgrade).is_none());
+        });
+        assert_eq!(Sym::with_str("only-once").name(), "only-once");
+    }
+
+    #[test]
+    fn display_plain_syms() {
+        assert_eq!(format!("{}", Sym::with_str("abc")), "abc");
+        assert_eq!(format!("{}", Sym::with_str("...")), "...");
+    }
+
+    #[test]
+    fn display_syms_that_need_pipes() {
+        assert_eq!(format!("{}", Sym::with_str("hello world")), "|hello world|");
+        assert_eq!(format!("{}", Sym::with_str("")), "||");
+        assert_eq!(format!("{}", Sym::with_str("+i")), "|+i|");
+        assert_eq!(format!("{}", Sym::with_str("a|b\\c")), "|a\\|b\\\\c|");
+        assert_eq!(format!("{}", Sym::with_str("a\tb\x01")), "|a\\tb\\x1;|");
+    }
 }
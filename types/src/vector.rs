@@ -0,0 +1,104 @@
+/* types/src/vector.rs
+ * Eryn Wells <eryn@erynwells.me>
+ */
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::vec;
+use object::{Obj, Object};
+use printer::Printer;
+
+/// A Scheme vector: a fixed-length sequence of objects, written `#(a b c)`. Items can be changed
+/// in place.
+#[derive(Debug, PartialEq)]
+pub struct Vector(RefCell<Vec<Obj>>);
+
+impl Vector {
+    pub fn new(items: Vec<Obj>) -> Vector {
+        Vector(RefCell::new(items))
+    }
+
+    pub fn empty() -> Vector {
+        Vector::new(Vec::new())
+    }
+
+    pub fn len(&self) -> usize { self.0.borrow().len() }
+    pub fn is_empty(&self) -> bool { self.0.borrow().is_empty() }
+    pub fn get(&self, index: usize) -> Option<Obj> { self.0.borrow().get(index).cloned() }
+
+    /// Replace the item at `index`. Returns false if `index` is out of range.
+    pub fn set(&self, index: usize, obj: Obj) -> bool {
+        match self.0.borrow_mut().get_mut(index) {
+            Some(item) => {
+                *item = obj;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// The items in the vector as it is now.
+    pub fn iter(&self) -> vec::IntoIter<Obj> { self.0.borrow().clone().into_iter() }
+}
+
+impl Object for Vector {
+    fn as_any(&self) -> &Any { self }
+    fn as_vector(&self) -> Option<&Vector> { Some(self) }
+
+    fn trace(&self, visit: &mut FnMut(&Obj)) {
+        for item in self.0.borrow().iter() {
+            visit(item);
+        }
+    }
+
+    fn unlink(&self) {
+        self.0.borrow_mut().clear();
+    }
+}
+
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Printer::new(self).print(self, f)
+    }
+}
+
+impl PartialEq<Obj> for Vector {
+    fn eq(&self, rhs: &Obj) -> bool {
+        match rhs.obj().and_then(Object::as_vector) {
+            Some(rhs_vector) => self == rhs_vector,
+            None => false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vector;
+    use bool::Bool;
+    use object::Obj;
+    use sym::Sym;
+
+    #[test]
+    fn display_empty_vector() {
+        assert_eq!(format!("{}", Vector::empty()), "#()");
+    }
+
+    #[test]
+    fn display_vector() {
+        let v = Vector::new(vec![Obj::new(Sym::with_str("abc")),
+                                 Obj::new(Bool::True),
+                                 Obj::Null]);
+        assert_eq!(format!("{}", v), "#(abc #t ())");
+    }
+
+    #[test]
+    fn eq_vectors() {
+        let a = Obj::new(Vector::new(vec![Obj::new(Sym::with_str("abc"))]));
+        let b = Obj::new(Vector::new(vec![Obj::new(Sym::with_str("abc"))]));
+        let c = Obj::new(Vector::new(vec![Obj::new(Sym::with_str("def"))]));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, Obj::new(Vector::empty()));
+    }
+}